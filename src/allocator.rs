@@ -1,4 +1,6 @@
 use std::alloc::{self, Layout};
+#[cfg(test)]
+use std::{cell::Cell, rc::Rc};
 
 /// An object which allocates memory for use.
 ///
@@ -83,9 +85,40 @@ pub unsafe trait Allocator {
     ///
     /// `p` must be a valid pointer
     unsafe fn deallocate_raw_aligned(&mut self, p: *mut (), n: usize, align: usize);
+
+    /// Returns true if memory allocated by `other` can be de-allocated by this
+    /// allocator, so containers can hand whole buffers to each other instead
+    /// of moving their elements. Zero-sized allocators have no state, so they
+    /// are compatible by default
+    ///
+    /// # Arguments
+    ///
+    /// `other`: The other allocator
+    fn is_compatible(&self, other: &Self) -> bool
+    where
+        Self: Sized,
+    {
+        let _ = other;
+        std::mem::size_of::<Self>() == 0
+    }
+
+    /// Returns the number of bytes actually usable in a block allocated with
+    /// `n` bytes aligned to `align` bytes, which is at least `n`. Allocators
+    /// which round requests up can report the slack so containers can use it.
+    /// A block may be deallocated with any size from `n` up to its usable size
+    ///
+    /// # Arguments
+    ///
+    /// `n`: The number of bytes requested
+    ///
+    /// `align`: The alignment requested
+    fn usable_size(&self, n: usize, align: usize) -> usize {
+        let _ = align;
+        n
+    }
 }
 
-#[derive(Default)]
+#[derive(Clone, Default)]
 pub struct DefaultAllocator {
     // padding due to 1-size struct in C
     _dummy: u8,
@@ -108,11 +141,266 @@ unsafe impl Allocator for DefaultAllocator {
             Layout::array::<u8>(n).unwrap().align_to(align).unwrap(),
         )
     }
+
+    fn is_compatible(&self, _other: &Self) -> bool {
+        // all default allocators share the global heap
+        true
+    }
+}
+
+/// A link in a pool's free list, stored inside the free block itself
+struct FreeBlock {
+    next: *mut FreeBlock,
+}
+
+/// An allocator which hands out fixed-size blocks of `BLOCK` bytes, carved out
+/// of larger chunks from an inner allocator. Freed blocks are recycled, and a
+/// new chunk is allocated only once the existing ones are exhausted. Requests
+/// which don't fit in a block, like a vector's buffer, go directly to the inner
+/// allocator. This suits node-based containers like `List` and `HashMap`
+pub struct PoolAllocator<const BLOCK: usize, A: Allocator = DefaultAllocator> {
+    free_list: *mut FreeBlock,
+    /// The most recent chunk. The first block of each chunk links to the previous one
+    chunks: *mut FreeBlock,
+    /// The next never-used block in the most recent chunk
+    next: *mut u8,
+    /// The end of the most recent chunk
+    end: *mut u8,
+    blocks_per_chunk: usize,
+    inner: A,
+}
+
+impl<const BLOCK: usize, A: Allocator> PoolAllocator<BLOCK, A> {
+    /// The distance between blocks, which leaves room for a free list link
+    const STRIDE: usize = {
+        let size = if BLOCK > std::mem::size_of::<FreeBlock>() {
+            BLOCK
+        } else {
+            std::mem::size_of::<FreeBlock>()
+        };
+        size.next_multiple_of(std::mem::align_of::<FreeBlock>())
+    };
+
+    /// The alignment of every block, which is the largest power of two that
+    /// divides the stride, up to 16
+    const ALIGN: usize = {
+        let align = 1 << Self::STRIDE.trailing_zeros();
+        if align > 16 {
+            16
+        } else {
+            align
+        }
+    };
+
+    /// Creates a pool allocator which allocates its chunks from another allocator
+    ///
+    /// # Arguments
+    ///
+    /// `inner`: The allocator used for chunks and for requests which don't fit in a block
+    ///
+    /// `blocks_per_chunk`: The number of blocks to allocate at once
+    pub fn new_in(inner: A, blocks_per_chunk: usize) -> Self {
+        assert_ne!(blocks_per_chunk, 0, "`blocks_per_chunk` must not be zero!");
+
+        Self {
+            free_list: std::ptr::null_mut(),
+            chunks: std::ptr::null_mut(),
+            next: std::ptr::null_mut(),
+            end: std::ptr::null_mut(),
+            blocks_per_chunk,
+            inner,
+        }
+    }
+
+    /// Returns true if a request is served from the pool rather than the inner allocator
+    ///
+    /// # Arguments
+    ///
+    /// `n`: The number of bytes requested
+    ///
+    /// `align`: The alignment requested
+    fn fits_block(n: usize, align: usize) -> bool {
+        n <= BLOCK && align <= Self::ALIGN
+    }
+
+    /// The size of each chunk in bytes, including the block which links the chunks
+    fn chunk_size(&self) -> usize {
+        (self.blocks_per_chunk + 1) * Self::STRIDE
+    }
+
+    /// Allocates a new chunk, making its blocks available
+    fn allocate_chunk(&mut self) {
+        let chunk = self
+            .inner
+            .allocate_raw_aligned(self.chunk_size(), Self::ALIGN)
+            .cast::<u8>();
+        unsafe {
+            // the first block links to the previous chunk so they can all be freed
+            chunk
+                .cast::<FreeBlock>()
+                .write(FreeBlock { next: self.chunks });
+            self.chunks = chunk.cast();
+            self.next = chunk.add(Self::STRIDE);
+            self.end = chunk.add(self.chunk_size());
+        }
+    }
+}
+
+impl<const BLOCK: usize, A: Allocator + Default> Default for PoolAllocator<BLOCK, A> {
+    fn default() -> Self {
+        Self::new_in(A::default(), 32)
+    }
+}
+
+unsafe impl<const BLOCK: usize, A: Allocator> Allocator for PoolAllocator<BLOCK, A> {
+    fn allocate_raw_aligned(&mut self, n: usize, align: usize) -> *mut () {
+        if !Self::fits_block(n, align) {
+            return self.inner.allocate_raw_aligned(n, align);
+        }
+
+        // recycle a freed block if there is one
+        if let Some(block) = unsafe { self.free_list.as_mut() } {
+            self.free_list = block.next;
+            return (block as *mut FreeBlock).cast();
+        }
+
+        if self.next == self.end {
+            self.allocate_chunk();
+        }
+        let block = self.next;
+        self.next = unsafe { self.next.add(Self::STRIDE) };
+        block.cast()
+    }
+
+    unsafe fn deallocate_raw_aligned(&mut self, p: *mut (), n: usize, align: usize) {
+        if !Self::fits_block(n, align) {
+            return self.inner.deallocate_raw_aligned(p, n, align);
+        }
+
+        let block = p.cast::<FreeBlock>();
+        block.write(FreeBlock {
+            next: self.free_list,
+        });
+        self.free_list = block;
+    }
+
+    fn is_compatible(&self, other: &Self) -> bool {
+        // blocks can only be returned to the pool which carved them out
+        std::ptr::eq(self, other)
+    }
+
+    fn usable_size(&self, n: usize, align: usize) -> usize {
+        if Self::fits_block(n, align) {
+            BLOCK
+        } else {
+            self.inner.usable_size(n, align)
+        }
+    }
+}
+
+impl<const BLOCK: usize, A: Allocator> Drop for PoolAllocator<BLOCK, A> {
+    fn drop(&mut self) {
+        let chunk_size = self.chunk_size();
+        while !self.chunks.is_null() {
+            unsafe {
+                let previous = (*self.chunks).next;
+                self.inner
+                    .deallocate_raw_aligned(self.chunks.cast(), chunk_size, Self::ALIGN);
+                self.chunks = previous;
+            }
+        }
+    }
+}
+
+unsafe impl<const BLOCK: usize, A: Allocator + Send> Send for PoolAllocator<BLOCK, A> {}
+
+/// An allocator which aligns every allocation to at least `ALIGN` bytes,
+/// regardless of the alignment of the elements. This is useful for buffers
+/// which are accessed with SIMD instructions, such as a `Vector<f32>`. The
+/// memory itself comes from an inner allocator. `ALIGN` must be a power of two
+#[derive(Default)]
+pub struct AlignedAllocator<const ALIGN: usize, A: Allocator = DefaultAllocator> {
+    inner: A,
+}
+
+impl<const ALIGN: usize, A: Allocator> AlignedAllocator<ALIGN, A> {
+    /// Creates an aligned allocator which allocates from another allocator
+    ///
+    /// # Arguments
+    ///
+    /// `inner`: The allocator used for the aligned allocations
+    pub fn new_in(inner: A) -> Self {
+        Self { inner }
+    }
+}
+
+unsafe impl<const ALIGN: usize, A: Allocator> Allocator for AlignedAllocator<ALIGN, A> {
+    fn allocate_raw_aligned(&mut self, n: usize, align: usize) -> *mut () {
+        self.inner.allocate_raw_aligned(n, align.max(ALIGN))
+    }
+
+    unsafe fn deallocate_raw_aligned(&mut self, p: *mut (), n: usize, align: usize) {
+        // the block was allocated with the raised alignment
+        self.inner.deallocate_raw_aligned(p, n, align.max(ALIGN))
+    }
+
+    fn is_compatible(&self, other: &Self) -> bool {
+        self.inner.is_compatible(&other.inner)
+    }
+
+    fn usable_size(&self, n: usize, align: usize) -> usize {
+        self.inner.usable_size(n, align.max(ALIGN))
+    }
+}
+
+/// An allocator which keeps track of the number of allocations it has
+/// made and the number of bytes that are currently allocated. Clones
+/// share the same counters
+#[cfg(test)]
+#[derive(Clone, Default)]
+pub(crate) struct CountingAllocator {
+    allocations: Rc<Cell<usize>>,
+    live_bytes: Rc<Cell<usize>>,
+}
+
+#[cfg(test)]
+impl CountingAllocator {
+    /// The total number of allocations made
+    pub(crate) fn allocations(&self) -> usize {
+        self.allocations.get()
+    }
+
+    /// The number of bytes which are currently allocated
+    pub(crate) fn live_bytes(&self) -> usize {
+        self.live_bytes.get()
+    }
+}
+
+#[cfg(test)]
+unsafe impl Allocator for CountingAllocator {
+    fn allocate_raw_aligned(&mut self, n: usize, align: usize) -> *mut () {
+        self.allocations.set(self.allocations.get() + 1);
+        self.live_bytes.set(self.live_bytes.get() + n);
+        DefaultAllocator::default().allocate_raw_aligned(n, align)
+    }
+
+    unsafe fn deallocate_raw_aligned(&mut self, p: *mut (), n: usize, align: usize) {
+        self.live_bytes.set(self.live_bytes.get() - n);
+        DefaultAllocator::default().deallocate_raw_aligned(p, n, align)
+    }
+
+    fn is_compatible(&self, other: &Self) -> bool {
+        // keep the counts balanced by only sharing buffers between clones
+        Rc::ptr_eq(&self.live_bytes, &other.live_bytes)
+    }
 }
 
 #[cfg(test)]
 mod test {
-    use super::{Allocator, DefaultAllocator};
+    use super::{AlignedAllocator, Allocator, CountingAllocator, DefaultAllocator, PoolAllocator};
+    use crate::list::List;
+    use crate::vector::Vector;
+    use moveit::moveit;
 
     #[test]
     fn layout() {
@@ -135,4 +423,132 @@ mod test {
         assert_eq!((aligned_by_8 as usize) % 8, 0);
         assert_eq!((aligned_by_16 as usize) % 16, 0);
     }
+
+    #[test]
+    fn aligned() {
+        let mut v: Vector<f32, AlignedAllocator<32>> = Vector::new();
+        for n in 0..100 {
+            v.push(n as f32);
+            assert_eq!(v.as_ptr() as usize % 32, 0);
+        }
+        v.shrink_to_fit();
+        assert_eq!(v.as_ptr() as usize % 32, 0);
+
+        // deallocations must use the raised alignment too
+        let counter = CountingAllocator::default();
+        let mut alloc = AlignedAllocator::<64, _>::new_in(counter.clone());
+        let p = alloc.allocate_raw_aligned(12, 4);
+        assert_eq!(p as usize % 64, 0);
+        unsafe { alloc.deallocate_raw_aligned(p, 12, 4) };
+        assert_eq!(counter.live_bytes(), 0);
+    }
+
+    #[test]
+    fn is_compatible() {
+        assert!(DefaultAllocator::default().is_compatible(&DefaultAllocator::default()));
+
+        let first = PoolAllocator::<16>::default();
+        let second = PoolAllocator::<16>::default();
+        assert!(first.is_compatible(&first));
+        assert!(!first.is_compatible(&second));
+    }
+
+    #[test]
+    fn pool_recycles_blocks() {
+        let counter = CountingAllocator::default();
+        let mut pool = PoolAllocator::<16, _>::new_in(counter.clone(), 4);
+
+        let a = pool.allocate_raw_aligned(16, 8);
+        let b = pool.allocate_raw_aligned(16, 8);
+        assert_ne!(a, b);
+        assert_eq!(a as usize % 8, 0);
+        assert_eq!(counter.allocations(), 1);
+
+        unsafe { pool.deallocate_raw_aligned(a, 16, 8) };
+        assert_eq!(pool.allocate_raw_aligned(16, 8), a);
+        assert_eq!(counter.allocations(), 1);
+
+        // the first chunk only holds 4 blocks
+        for _ in 0..3 {
+            pool.allocate_raw_aligned(16, 8);
+        }
+        assert_eq!(counter.allocations(), 2);
+
+        std::mem::drop(pool);
+        assert_eq!(counter.live_bytes(), 0);
+    }
+
+    #[test]
+    fn pool_forwards_large_requests() {
+        let counter = CountingAllocator::default();
+        let mut pool = PoolAllocator::<16, _>::new_in(counter.clone(), 4);
+
+        let p = pool.allocate_raw_aligned(64, 8);
+        assert_eq!(counter.allocations(), 1);
+        assert_eq!(counter.live_bytes(), 64);
+        unsafe { pool.deallocate_raw_aligned(p, 64, 8) };
+        assert_eq!(counter.live_bytes(), 0);
+    }
+
+    #[test]
+    fn pool_list_churn() {
+        let counter = CountingAllocator::default();
+        {
+            moveit! {
+                let mut list = unsafe {
+                    List::<u64, _>::new_in(PoolAllocator::<24, _>::new_in(counter.clone(), 16))
+                };
+            }
+            for round in 0..100 {
+                list.extend(0..10);
+                for n in 0..10 {
+                    assert_eq!(list.pop_front(), Some(n));
+                }
+                assert!(list.is_empty(), "round {round}");
+            }
+            // every node fit in the first chunk
+            assert_eq!(counter.allocations(), 1);
+        }
+        assert_eq!(counter.live_bytes(), 0);
+    }
+
+    /// An allocator which rounds every request up to a power of two
+    #[derive(Default)]
+    struct PowerOfTwoAllocator;
+
+    unsafe impl Allocator for PowerOfTwoAllocator {
+        fn allocate_raw_aligned(&mut self, n: usize, align: usize) -> *mut () {
+            DefaultAllocator::default().allocate_raw_aligned(self.usable_size(n, align), align)
+        }
+
+        unsafe fn deallocate_raw_aligned(&mut self, p: *mut (), n: usize, align: usize) {
+            DefaultAllocator::default().deallocate_raw_aligned(p, self.usable_size(n, align), align)
+        }
+
+        fn usable_size(&self, n: usize, _align: usize) -> usize {
+            n.next_power_of_two()
+        }
+    }
+
+    #[test]
+    fn usable_size() {
+        let mut v: Vector<u32, PowerOfTwoAllocator> = Vector::new();
+        v.reserve(5);
+        assert_eq!(v.capacity(), 8);
+
+        // the slack is used before growing again
+        (0..8).for_each(|n| v.push(n));
+        assert_eq!(v.capacity(), 8);
+        v.push(8);
+        assert_eq!(v.capacity(), 16);
+        assert!(v.iter().copied().eq(0..9));
+    }
+
+    #[test]
+    fn pool_usable_size() {
+        let pool = PoolAllocator::<24, DefaultAllocator>::default();
+        assert_eq!(pool.usable_size(10, 8), 24);
+        assert_eq!(pool.usable_size(100, 8), 100);
+        assert_eq!(DefaultAllocator::default().usable_size(10, 8), 10);
+    }
 }
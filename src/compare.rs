@@ -1,10 +1,26 @@
+use std::borrow::Borrow;
+use std::cmp::Ordering;
 use std::marker::PhantomData;
 
 /// A comparator trait which compares two nodes
-pub trait Compare<T> {
+pub trait Compare<T: ?Sized> {
     /// Compare two values, and return true if
     /// `left` is lesser to `right`
     fn compare(left: &T, right: &T) -> bool;
+
+    /// Compare two values, and return their ordering. The
+    /// default implementation calls `compare` up to twice,
+    /// so implementors should override it when they can
+    /// order the values in one go
+    fn cmp(left: &T, right: &T) -> Ordering {
+        if Self::compare(left, right) {
+            Ordering::Less
+        } else if Self::compare(right, left) {
+            Ordering::Greater
+        } else {
+            Ordering::Equal
+        }
+    }
 }
 
 /// A struct that implements `Compare` for `T`, and
@@ -14,10 +30,21 @@ pub struct Greater<T> {
     _marker: PhantomData<T>,
 }
 
-impl<T: PartialOrd> Compare<T> for Greater<T> {
-    fn compare(left: &T, right: &T) -> bool {
+impl<T: Borrow<Q>, Q: ?Sized + PartialOrd> Compare<Q> for Greater<T> {
+    fn compare(left: &Q, right: &Q) -> bool {
         left > right
     }
+
+    fn cmp(left: &Q, right: &Q) -> Ordering {
+        // incomparable values are neither greater nor lesser
+        right.partial_cmp(left).unwrap_or(Ordering::Equal)
+    }
+}
+
+impl<T> Clone for Greater<T> {
+    fn clone(&self) -> Self {
+        Self::default()
+    }
 }
 
 impl<T> Default for Greater<T> {
@@ -36,10 +63,21 @@ pub struct Less<T> {
     _marker: PhantomData<T>,
 }
 
-impl<T: PartialOrd> Compare<T> for Less<T> {
-    fn compare(left: &T, right: &T) -> bool {
+impl<T: Borrow<Q>, Q: ?Sized + PartialOrd> Compare<Q> for Less<T> {
+    fn compare(left: &Q, right: &Q) -> bool {
         left < right
     }
+
+    fn cmp(left: &Q, right: &Q) -> Ordering {
+        // incomparable values are neither greater nor lesser
+        left.partial_cmp(right).unwrap_or(Ordering::Equal)
+    }
+}
+
+impl<T> Clone for Less<T> {
+    fn clone(&self) -> Self {
+        Self::default()
+    }
 }
 
 impl<T> Default for Less<T> {
@@ -50,3 +88,40 @@ impl<T> Default for Less<T> {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::{Compare, Greater, Less};
+    use std::cmp::Ordering;
+
+    /// A comparator which only provides `compare`
+    struct ByLen;
+
+    impl Compare<&str> for ByLen {
+        fn compare(left: &&str, right: &&str) -> bool {
+            left.len() < right.len()
+        }
+    }
+
+    #[test]
+    fn less_cmp() {
+        assert_eq!(Less::<u32>::cmp(&1, &2), Ordering::Less);
+        assert_eq!(Less::<u32>::cmp(&2, &1), Ordering::Greater);
+        assert_eq!(Less::<u32>::cmp(&2, &2), Ordering::Equal);
+        assert_eq!(Less::<f32>::cmp(&f32::NAN, &1.0), Ordering::Equal);
+    }
+
+    #[test]
+    fn greater_cmp() {
+        assert_eq!(Greater::<u32>::cmp(&1, &2), Ordering::Greater);
+        assert_eq!(Greater::<u32>::cmp(&2, &1), Ordering::Less);
+        assert_eq!(Greater::<u32>::cmp(&2, &2), Ordering::Equal);
+    }
+
+    #[test]
+    fn default_cmp() {
+        assert_eq!(ByLen::cmp(&"a", &"bc"), Ordering::Less);
+        assert_eq!(ByLen::cmp(&"bc", &"a"), Ordering::Greater);
+        assert_eq!(ByLen::cmp(&"ab", &"cd"), Ordering::Equal);
+    }
+}
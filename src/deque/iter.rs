@@ -104,6 +104,7 @@ pub struct RawIter<'a, T: 'a> {
     last: *mut T,
     last_arr: *mut *mut T,
     subarray_size: usize,
+    remaining: usize,
     _marker: PhantomData<&'a T>,
 }
 
@@ -122,9 +123,14 @@ impl<'a, T: 'a> Iterator for RawIter<'a, T> {
             } else {
                 self.current = unsafe { self.current.add(1) }
             }
+            self.remaining -= 1;
             elem
         }
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
 }
 
 impl<'a, T: 'a> DoubleEndedIterator for RawIter<'a, T> {
@@ -140,12 +146,39 @@ impl<'a, T: 'a> DoubleEndedIterator for RawIter<'a, T> {
             } else {
                 self.last = unsafe { self.last.sub(1) };
             }
+            self.remaining -= 1;
             unsafe { self.last.as_mut() }
         }
     }
 }
 
+impl<'a, T: 'a> ExactSizeIterator for RawIter<'a, T> {}
+
 impl<'a, T: 'a> RawIter<'a, T> {
+    /// Counts the elements between two positions, like `Deque::len`
+    ///
+    /// # Arguments
+    ///
+    /// `begin`: The begin iterator
+    ///
+    /// `end`: The end iterator
+    ///
+    /// # Safety
+    ///
+    /// `begin` and `end` must point to valid portions of the deque, and end must be after begin
+    unsafe fn distance(begin: &CompatIter<'a, T>, end: &CompatIter<'a, T>) -> usize {
+        if begin.current_array == end.current_array {
+            end.current.offset_from(begin.current) as usize
+        } else {
+            let subarray_size = begin.end.offset_from(begin.begin);
+            let full_subarray_diff =
+                end.current_array.offset_from(begin.current_array) * subarray_size;
+            let begin_subarray_offset = begin.current.offset_from(begin.begin);
+            let end_subarray_offset = end.current.offset_from(end.begin);
+            (full_subarray_diff - begin_subarray_offset + end_subarray_offset) as usize
+        }
+    }
+
     /// Transforms a raw iterator into the compatible component parts
     fn into_compat(self) -> (CompatIter<'a, T>, CompatIter<'a, T>) {
         (
@@ -210,6 +243,7 @@ impl<'a, T: 'a> RawIter<'a, T> {
             last: end.current as *mut T,
             last_arr: end.current_array as *mut *mut T,
             subarray_size: unsafe { begin.end.offset_from(begin.begin) } as usize,
+            remaining: unsafe { Self::distance(&begin, &end) },
             _marker: PhantomData,
         }
     }
@@ -226,12 +260,14 @@ impl<'a, T: 'a> RawIter<'a, T> {
     ///
     /// `begin` and `end` must point to valid portions of the deque, and end must be after begin
     unsafe fn from_compat_mut(begin: CompatIterMut<'a, T>, end: CompatIterMut<'a, T>) -> Self {
+        let remaining = unsafe { Self::distance(&(&begin).into(), &(&end).into()) };
         Self {
             current: begin.current,
             current_arr: begin.current_array,
             last: end.current,
             last_arr: end.current_array,
             subarray_size: unsafe { begin.end.offset_from(begin.begin) } as usize,
+            remaining,
             _marker: PhantomData,
         }
     }
@@ -272,8 +308,14 @@ impl<'a, T: 'a> Iterator for Iter<'a, T> {
     fn next(&mut self) -> Option<Self::Item> {
         self.raw.next().map(|r| &*r)
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.raw.size_hint()
+    }
 }
 
+impl<'a, T: 'a> ExactSizeIterator for Iter<'a, T> {}
+
 impl<'a, T: 'a> DoubleEndedIterator for Iter<'a, T> {
     fn next_back(&mut self) -> Option<Self::Item> {
         self.raw.next_back().map(|r| &*r)
@@ -320,8 +362,14 @@ impl<'a, T: 'a> Iterator for IterMut<'a, T> {
     fn next(&mut self) -> Option<Self::Item> {
         self.raw.next()
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.raw.size_hint()
+    }
 }
 
+impl<'a, T: 'a> ExactSizeIterator for IterMut<'a, T> {}
+
 impl<'a, T: 'a> DoubleEndedIterator for IterMut<'a, T> {
     fn next_back(&mut self) -> Option<Self::Item> {
         self.raw.next_back()
@@ -367,11 +415,50 @@ impl<'a, T: 'a, A: Allocator> IntoIterator for Queue<'a, T, A> {
     }
 }
 
+// the iterators' lifetimes are shortened to the borrow, since
+// `iter` and `iter_mut` hand out the deque's own lifetime
+impl<'b, 'a: 'b, T: 'a, A: Allocator> IntoIterator for &'b Deque<'a, T, A> {
+    type Item = &'b T;
+    type IntoIter = Iter<'b, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'b, 'a: 'b, T: 'a, A: Allocator> IntoIterator for &'b mut Deque<'a, T, A> {
+    type Item = &'b mut T;
+    type IntoIter = IterMut<'b, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl<'b, 'a: 'b, T: 'a, A: Allocator> IntoIterator for &'b Queue<'a, T, A> {
+    type Item = &'b T;
+    type IntoIter = Iter<'b, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'b, 'a: 'b, T: 'a, A: Allocator> IntoIterator for &'b mut Queue<'a, T, A> {
+    type Item = &'b mut T;
+    type IntoIter = IterMut<'b, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
 #[cfg(test)]
 mod test {
 
     use crate::deque::iter::CompatIterMut;
     use crate::deque::DefaultDeque;
+    use crate::queue::DefaultQueue;
     use memoffset::offset_of;
 
     #[test]
@@ -442,4 +529,66 @@ mod test {
         assert_eq!(i.next(), None);
         assert_eq!(i.next_back(), None);
     }
+
+    #[test]
+    fn iter_len() {
+        let mut d = DefaultDeque::new();
+        for i in 0..70 {
+            d.push_front(i);
+            d.push_back(i);
+        }
+
+        let mut i = d.iter();
+        assert_eq!(i.len(), 140);
+        assert_eq!(i.size_hint(), (140, Some(140)));
+
+        i.nth(9);
+        assert_eq!(i.len(), 130);
+        i.nth_back(69);
+        assert_eq!(i.size_hint(), (60, Some(60)));
+        assert_eq!(i.count(), 60);
+
+        let mut i = d.iter_mut();
+        i.next();
+        i.next_back();
+        assert_eq!(i.len(), 138);
+    }
+
+    #[test]
+    fn empty_iter_len() {
+        let d = DefaultDeque::<u32>::new();
+        assert_eq!(d.iter().len(), 0);
+    }
+
+    #[test]
+    fn into_iter_ref() {
+        let mut d: DefaultDeque<u32> = (0..100).collect();
+        let mut sum = 0;
+        for x in &d {
+            sum += x;
+        }
+        assert_eq!(sum, 4950);
+
+        for x in &mut d {
+            *x *= 2;
+        }
+        // the deque is usable again once the borrow ends
+        d.push_back(200);
+        assert!(d.iter().copied().eq((0..=100).map(|n| n * 2)));
+    }
+
+    #[test]
+    fn into_iter_ref_queue() {
+        let mut q: DefaultQueue<u32> = (0..10).collect();
+        for x in &mut q {
+            *x += 1;
+        }
+        q.push(11);
+
+        let mut seen = Vec::new();
+        for x in &q {
+            seen.push(*x);
+        }
+        assert_eq!(seen, (1..=11).collect::<Vec<u32>>());
+    }
 }
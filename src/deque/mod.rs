@@ -2,6 +2,7 @@ use crate::allocator::{Allocator, DefaultAllocator};
 use crate::deque::iter::{CompatIterMut, Iter, IterMut};
 use crate::util::rotate;
 use itertools::Itertools;
+use std::cmp::Ordering;
 use std::fmt::{Debug, Formatter};
 
 pub mod iter;
@@ -33,6 +34,29 @@ impl<'a, T: 'a, A: Allocator> Deque<'a, T, A> {
     const INITIAL_PTR_ARRAY_SIZE: u32 = 8;
     const SUBARRAY_SIZE: usize = Self::calculate_subarray_size();
 
+    /// Moves all the elements of `other` to the back of the deque, leaving
+    /// `other` empty. Elements sit at fixed offsets within their subarrays, so
+    /// the subarrays can't be spliced and this is O(n). If the allocators are
+    /// compatible, only the shorter of the two deques has its elements moved
+    ///
+    /// # Arguments
+    ///
+    /// `other`: The deque to move elements from
+    pub fn append(&mut self, other: &mut Self) {
+        if self.len() < other.len() && self.allocator.is_compatible(&other.allocator) {
+            // move our elements onto the front of `other`, then trade places
+            while let Some(elem) = self.pop_back() {
+                other.push_front(elem);
+            }
+            std::mem::swap(self, other);
+            return;
+        }
+
+        while let Some(elem) = other.pop_front() {
+            self.push_back(elem);
+        }
+    }
+
     /// Provides a reference to the back element, or `None` if the deque is empty.
     pub fn back(&self) -> Option<&T> {
         self.iter().next_back()
@@ -53,6 +77,26 @@ impl<'a, T: 'a, A: Allocator> Deque<'a, T, A> {
         self.iter_mut().next()
     }
 
+    /// Provides a reference to the element at `index`, or `None` if it is out
+    /// of bounds. Element at index 0 is the front of the queue.
+    ///
+    /// # Arguments
+    ///
+    /// `index`: The index of the element
+    pub fn get(&self, index: usize) -> Option<&T> {
+        self.elem_ptr(index).map(|elem| unsafe { &*elem })
+    }
+
+    /// Provides a mutable reference to the element at `index`, or `None` if it
+    /// is out of bounds. Element at index 0 is the front of the queue.
+    ///
+    /// # Arguments
+    ///
+    /// `index`: The index of the element
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        self.elem_ptr(index).map(|elem| unsafe { &mut *elem })
+    }
+
     /// Returns true if the deque contains no elements
     pub fn is_empty(&self) -> bool {
         self.begin_it.current == self.end_it.current
@@ -163,6 +207,15 @@ impl<'a, T: 'a, A: Allocator> Deque<'a, T, A> {
         }
     }
 
+    /// Returns the index of the first element satisfying a predicate, if any
+    ///
+    /// # Arguments
+    ///
+    /// `f`: The predicate
+    pub fn position<F: FnMut(&T) -> bool>(&self, f: F) -> Option<usize> {
+        self.iter().position(f)
+    }
+
     /// Pushes an element to the back of the deque
     ///
     /// # Arguments
@@ -225,6 +278,33 @@ impl<'a, T: 'a, A: Allocator> Deque<'a, T, A> {
         }
     }
 
+    /// Pushes each element of an iterator to the front of the deque, in
+    /// order. The elements end up reversed at the front, as if they were
+    /// pushed with `push_front` one at a time
+    ///
+    /// # Arguments
+    ///
+    /// `iter`: The elements to push
+    pub fn extend_front<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let iter = iter.into_iter();
+        // make sure there are enough pointers at the front for the expected
+        // subarrays, so we reallocate the pointer array at most once
+        let free_in_subarray =
+            unsafe { self.begin_it.current.offset_from(self.begin_it.begin) } as usize;
+        let needed_subarrays = iter
+            .size_hint()
+            .0
+            .saturating_sub(free_in_subarray)
+            .div_ceil(Self::SUBARRAY_SIZE);
+        let unused_ptrs_at_front =
+            unsafe { self.begin_it.current_array.offset_from(self.ptr_array) } as usize;
+        if needed_subarrays > unused_ptrs_at_front {
+            self.realloc_ptr_array(needed_subarrays - unused_ptrs_at_front, true);
+        }
+
+        iter.for_each(|elem| self.push_front(elem));
+    }
+
     /// Removes and returns the element at `index` from the deque.
     /// Whichever end is closer to the removal point will be moved to make
     /// room, and all the affected elements will be moved to new positions.
@@ -254,6 +334,102 @@ impl<'a, T: 'a, A: Allocator> Deque<'a, T, A> {
         }
     }
 
+    /// Retains only the elements that satisfy a predicate, preserving their order
+    ///
+    /// # Arguments
+    ///
+    /// `f`: The predicate, which returns false for elements to remove
+    pub fn retain<F: FnMut(&T) -> bool>(&mut self, mut f: F) {
+        let len = self.len();
+        let mut kept = 0;
+        {
+            // compact the retained elements towards the front. the write cursor never passes
+            // the read cursor, so every element is visited exactly once
+            let mut write = unsafe { self.iter_mut_unchecked() };
+            for (index, elem) in unsafe { self.iter_mut_unchecked() }.enumerate() {
+                if f(elem) {
+                    let dst: *mut T = write.next().unwrap();
+                    if kept != index {
+                        unsafe { std::ptr::swap(dst, elem) };
+                    }
+                    kept += 1;
+                }
+            }
+        }
+        // the removed elements have been moved to the back
+        for _ in kept..len {
+            self.pop_back();
+        }
+    }
+
+    /// Shrinks the pointer array to snugly fit the subarrays in use. Empty
+    /// subarrays are already released as elements are popped, so this only
+    /// needs to trim the pointer array itself
+    pub fn shrink_to_fit(&mut self) {
+        let used_ptrs = (unsafe {
+            self.end_it
+                .current_array
+                .offset_from(self.begin_it.current_array)
+        } + 1) as usize;
+        // keep a spare pointer at each end so the next push doesn't need to reallocate
+        let new_ptr_array_size = (used_ptrs + 2).max(Self::INITIAL_PTR_ARRAY_SIZE as usize);
+        if new_ptr_array_size >= self.ptr_array_size as usize {
+            return;
+        }
+
+        let current_array_start =
+            unsafe { self.begin_it.current_array.offset_from(self.ptr_array) } as usize;
+        let new_ptr_array = self.allocate_ptr_array(new_ptr_array_size);
+        new_ptr_array.fill_with(std::ptr::null_mut);
+
+        // keep the used subarrays centered
+        let new_array_start = (new_ptr_array_size - used_ptrs) / 2;
+        unsafe {
+            new_ptr_array
+                .as_mut_ptr()
+                .add(new_array_start)
+                .copy_from_nonoverlapping(self.ptr_array.add(current_array_start), used_ptrs)
+        };
+
+        self.free_ptr_array();
+        self.ptr_array = new_ptr_array.as_mut_ptr();
+        self.ptr_array_size = new_ptr_array_size as u32;
+
+        // update the iterators. the subarrays themselves haven't moved, so `current` is intact
+        unsafe {
+            self.begin_it
+                .set_subarray(self.ptr_array.add(new_array_start), Self::SUBARRAY_SIZE);
+            self.end_it.set_subarray(
+                self.ptr_array.add(new_array_start + used_ptrs - 1),
+                Self::SUBARRAY_SIZE,
+            );
+        }
+    }
+
+    /// Shortens the deque to its first `len` elements, dropping the rest.
+    /// Does nothing if the deque is already shorter
+    ///
+    /// # Arguments
+    ///
+    /// `len`: The number of elements to keep
+    pub fn truncate_back(&mut self, len: usize) {
+        for _ in len..self.len() {
+            self.pop_back();
+        }
+    }
+
+    /// Shortens the deque to its last `len` elements, dropping the rest.
+    /// Does nothing if the deque is already shorter
+    ///
+    /// # Arguments
+    ///
+    /// `len`: The number of elements to keep
+    pub fn truncate_front(&mut self, len: usize) {
+        for _ in len..self.len() {
+            self.pop_front();
+        }
+    }
+
     /// Allocates the subarray pointer array
     ///
     /// # Arguments
@@ -290,6 +466,29 @@ impl<'a, T: 'a, A: Allocator> Deque<'a, T, A> {
         unsafe { self.allocator.deallocate(subarray, Self::SUBARRAY_SIZE) }
     }
 
+    /// Finds the pointer to the element at `index` in O(1), or
+    /// `None` if it is out of bounds
+    ///
+    /// # Arguments
+    ///
+    /// `index`: The index of the element
+    fn elem_ptr(&self, index: usize) -> Option<*mut T> {
+        if index >= self.len() {
+            return None;
+        }
+
+        // offset from the start of the front subarray, which may be partially used
+        let offset =
+            index + unsafe { self.begin_it.current.offset_from(self.begin_it.begin) } as usize;
+        unsafe {
+            let subarray = *self
+                .begin_it
+                .current_array
+                .add(offset / Self::SUBARRAY_SIZE);
+            Some(subarray.add(offset % Self::SUBARRAY_SIZE))
+        }
+    }
+
     /// Calculates the size of each sub-array
     const fn calculate_subarray_size() -> usize {
         let elem_size = std::mem::size_of::<T>();
@@ -370,8 +569,8 @@ impl<'a, T: 'a, A: Allocator> Deque<'a, T, A> {
             // if there's a lot of extra space then they are likely using the deque with heavy use
             // of `push_back` (like `queue`), so allocate them even more space. the + 1 is a
             // difference to the official implementation to hit this optimization earlier
-            if additional_capacity < (unused_ptrs_at_front + 1) / 2 {
-                additional_capacity = (unused_ptrs_at_front + 1) / 2;
+            if additional_capacity < unused_ptrs_at_front.div_ceil(2) {
+                additional_capacity = unused_ptrs_at_front.div_ceil(2);
             }
 
             new_array_start = unused_ptrs_at_front - additional_capacity;
@@ -421,6 +620,76 @@ impl<'a, T: 'a, A: Allocator> Deque<'a, T, A> {
     }
 }
 
+impl<'a, T: 'a, A: Allocator + Clone> Deque<'a, T, A> {
+    /// Splits the deque in two at `at`. The returned deque contains the
+    /// elements `[at, len)`, and the deque is left with `[0, at)`. Whichever
+    /// side is shorter is moved into a new deque
+    ///
+    /// # Arguments
+    ///
+    /// `at`: The index to split at
+    ///
+    /// # Panics
+    ///
+    /// Panics if `at` is greater than the length of the deque
+    pub fn split_off(&mut self, at: usize) -> Self {
+        let len = self.len();
+        assert!(
+            at <= len,
+            "split index (is {at}) should be <= len (is {len})"
+        );
+
+        let mut other = unsafe { Self::new_in(self.allocator.clone()) };
+        if at < len / 2 {
+            // move the front into the new deque, then trade places so the front is kept
+            for _ in 0..at {
+                other.push_back(self.pop_front().unwrap());
+            }
+            std::mem::swap(self, &mut other);
+        } else {
+            for _ in at..len {
+                other.push_front(self.pop_back().unwrap());
+            }
+        }
+        other
+    }
+}
+
+impl<'a, T: 'a + PartialEq, A: Allocator> Deque<'a, T, A> {
+    /// Returns true if the deque contains an element equal to `x`
+    ///
+    /// # Arguments
+    ///
+    /// `x`: The element to search for
+    pub fn contains(&self, x: &T) -> bool {
+        self.iter().any(|elem| elem == x)
+    }
+}
+
+impl<'a, T: 'a + Ord, A: Allocator> Deque<'a, T, A> {
+    /// Binary searches the deque for `x`. The deque must be sorted in
+    /// ascending order, otherwise the result is unspecified. Returns the index
+    /// of a matching element if one is found, or the index at which `x` could
+    /// be inserted to keep the deque sorted if not. If there are several
+    /// matching elements, any one of them may be returned
+    ///
+    /// # Arguments
+    ///
+    /// `x`: The element to search for
+    pub fn binary_search(&self, x: &T) -> Result<usize, usize> {
+        let (mut low, mut high) = (0, self.len());
+        while low < high {
+            let mid = low + (high - low) / 2;
+            match self.get(mid).expect("mid < len").cmp(x) {
+                Ordering::Less => low = mid + 1,
+                Ordering::Greater => high = mid,
+                Ordering::Equal => return Ok(mid),
+            }
+        }
+        Err(low)
+    }
+}
+
 impl<'a, T: 'a + Debug, A: Allocator> Debug for Deque<'a, T, A> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(f, "[ {:?} ]", self.iter().format(", "))
@@ -440,12 +709,12 @@ impl<'a, T: 'a, A: Allocator> Drop for Deque<'a, T, A> {
             unsafe { std::ptr::drop_in_place(elem as *mut T) }
         }
 
-        // free the sub-arrays
+        // free the sub-arrays, including the one the end iterator points into
         if let Some(current_array) = unsafe { self.begin_it.current_array.as_mut() } {
             for subarray in unsafe {
                 std::slice::from_raw_parts_mut(
                     current_array,
-                    self.end_it.current_array.offset_from(current_array) as usize,
+                    self.end_it.current_array.offset_from(current_array) as usize + 1,
                 )
             } {
                 self.free_subarray(*subarray);
@@ -466,8 +735,12 @@ impl<'a, T: 'a, A: Allocator + Default> FromIterator<T> for Deque<'a, T, A> {
 
 #[cfg(test)]
 mod test {
-    use crate::deque::DefaultDeque;
+    use crate::allocator::CountingAllocator;
+    use crate::deque::{DefaultDeque, Deque};
+    use crate::util::DropCounter;
     use memoffset::offset_of;
+    use std::cell::Cell;
+    use std::rc::Rc;
 
     #[test]
     fn layout() {
@@ -680,4 +953,347 @@ mod test {
 
         itertools::assert_equal(d, vec![0, 1, 2, 3, 5]);
     }
+
+    #[test]
+    fn shrink_to_fit() {
+        let mut d = (0..1000).collect::<DefaultDeque<_>>();
+        let old_ptr_array_size = d.ptr_array_size;
+        for _ in 0..990 {
+            d.pop_front();
+        }
+
+        d.shrink_to_fit();
+
+        assert!(d.ptr_array_size < old_ptr_array_size);
+        assert_eq!(d.ptr_array_size, 8);
+        assert_eq!(
+            unsafe { d.end_it.current_array.offset_from(d.begin_it.current_array) },
+            0
+        );
+        itertools::assert_equal(d.iter(), &(990..1000).collect::<Vec<_>>());
+
+        // make sure the deque is still usable at both ends
+        d.push_front(989);
+        d.push_back(1000);
+        itertools::assert_equal(d, 989..1001);
+    }
+
+    #[test]
+    fn extend_front() {
+        let mut d: DefaultDeque<_> = [3, 4].into_iter().collect();
+        d.extend_front([2, 1]);
+
+        assert_eq!(d.len(), 4);
+        assert!(d.iter().copied().eq([1, 2, 3, 4]));
+    }
+
+    #[test]
+    fn extend_front_across_subarrays() {
+        let mut d: DefaultDeque<u32> = [1000].into_iter().collect();
+        d.extend_front((0..1000).rev());
+
+        assert_eq!(d.len(), 1001);
+        assert!(d.iter().copied().eq(0..=1000));
+    }
+
+    #[test]
+    fn retain() {
+        let mut d: DefaultDeque<u32> = (0..100).collect();
+        d.retain(|n| n % 2 == 0);
+
+        assert_eq!(d.len(), 50);
+        assert!(d.iter().copied().eq((0..100).step_by(2)));
+    }
+
+    #[test]
+    fn retain_drops_once() {
+        let drops = Rc::new(Cell::new(0));
+        let mut d: DefaultDeque<_> = (0..100).map(|n| (n, DropCounter(drops.clone()))).collect();
+        d.retain(|(n, _)| n % 3 == 0);
+
+        assert_eq!(drops.get(), 66);
+        assert!(d.iter().map(|(n, _)| *n).eq((0..100).step_by(3)));
+
+        std::mem::drop(d);
+        assert_eq!(drops.get(), 100);
+    }
+
+    #[test]
+    fn truncate_back() {
+        let mut d: DefaultDeque<u32> = (0..100).collect();
+        d.truncate_back(200);
+        assert_eq!(d.len(), 100);
+
+        d.truncate_back(30);
+        assert_eq!(d.len(), 30);
+        assert!(d.iter().copied().eq(0..30));
+
+        d.truncate_back(0);
+        assert!(d.is_empty());
+    }
+
+    #[test]
+    fn truncate_front() {
+        let mut d: DefaultDeque<u32> = (0..100).collect();
+        d.truncate_front(30);
+        assert_eq!(d.len(), 30);
+        assert!(d.iter().copied().eq(70..100));
+
+        // the deque is still usable from both ends
+        d.push_front(69);
+        d.push_back(100);
+        assert!(d.iter().copied().eq(69..101));
+    }
+
+    #[test]
+    fn truncate_drops_once() {
+        let drops = Rc::new(Cell::new(0));
+        let mut d: DefaultDeque<_> = (0..100).map(|_| DropCounter(drops.clone())).collect();
+        d.truncate_back(60);
+        assert_eq!(drops.get(), 40);
+        d.truncate_front(10);
+        assert_eq!(drops.get(), 90);
+
+        std::mem::drop(d);
+        assert_eq!(drops.get(), 100);
+    }
+
+    #[test]
+    fn append() {
+        let mut d: DefaultDeque<u32> = (1..4).collect();
+        let mut other: DefaultDeque<u32> = (4..7).collect();
+        d.append(&mut other);
+
+        assert!(d.iter().copied().eq(1..7));
+        assert!(other.is_empty());
+        // the emptied deque is still usable
+        other.push_back(7);
+        assert_eq!(other.front(), Some(&7));
+    }
+
+    #[test]
+    fn append_counting() {
+        let allocator = CountingAllocator::default();
+        let mut d = unsafe { Deque::new_in(allocator.clone()) };
+        let mut other = unsafe { Deque::new_in(allocator.clone()) };
+        (1..4u32).for_each(|n| d.push_back(n));
+        (4..7u32).for_each(|n| other.push_back(n));
+        let allocations = allocator.allocations();
+
+        // the elements fit in the existing subarray, so nothing is allocated
+        d.append(&mut other);
+        assert_eq!(allocator.allocations(), allocations);
+        assert!(d.iter().copied().eq(1..7));
+        assert!(other.is_empty());
+
+        // every subarray and pointer array is freed exactly once
+        std::mem::drop(other);
+        std::mem::drop(d);
+        assert_eq!(allocator.live_bytes(), 0);
+    }
+
+    #[test]
+    fn append_moves_shorter() {
+        let allocator = CountingAllocator::default();
+        let mut d = unsafe { Deque::new_in(allocator.clone()) };
+        let mut other = unsafe { Deque::new_in(allocator.clone()) };
+        d.push_back(0u32);
+        (1..200u32).for_each(|n| other.push_back(n));
+        let allocations = allocator.allocations();
+
+        // only the single element is moved, onto a new subarray at the front of `other`,
+        // rather than the 199 elements needing several subarrays after `d`
+        d.append(&mut other);
+        assert_eq!(allocator.allocations(), allocations + 1);
+        assert!(d.iter().copied().eq(0..200));
+        assert!(other.is_empty());
+        other.push_back(200);
+        assert_eq!(other.front(), Some(&200));
+
+        std::mem::drop(other);
+        std::mem::drop(d);
+        assert_eq!(allocator.live_bytes(), 0);
+    }
+
+    #[test]
+    fn append_to_empty() {
+        let allocator = CountingAllocator::default();
+        let mut d = unsafe { Deque::new_in(allocator.clone()) };
+        let mut other = unsafe { Deque::new_in(allocator.clone()) };
+        (0..200u32).for_each(|n| other.push_back(n));
+        let allocations = allocator.allocations();
+
+        // the deques are swapped rather than moving each element
+        d.append(&mut other);
+        assert_eq!(allocator.allocations(), allocations);
+        assert!(d.iter().copied().eq(0..200));
+        assert!(other.is_empty());
+    }
+
+    #[test]
+    fn append_drops_once() {
+        let drops = Rc::new(Cell::new(0));
+        let mut d: DefaultDeque<_> = (0..3).map(|_| DropCounter(drops.clone())).collect();
+        let mut other: DefaultDeque<_> = (0..3).map(|_| DropCounter(drops.clone())).collect();
+        d.append(&mut other);
+        assert_eq!(drops.get(), 0);
+
+        std::mem::drop(other);
+        assert_eq!(drops.get(), 0);
+        std::mem::drop(d);
+        assert_eq!(drops.get(), 6);
+    }
+
+    #[test]
+    fn split_off() {
+        // the subarrays for `usize` hold 32 elements, so these cross several boundaries
+        for at in [0, 1, 31, 32, 100, 199, 200] {
+            let mut d: DefaultDeque<usize> = (0..200).collect();
+            let other = d.split_off(at);
+
+            assert_eq!(d.len(), at);
+            assert_eq!(other.len(), 200 - at);
+            assert!(d.iter().copied().eq(0..at));
+            assert!(other.iter().copied().eq(at..200));
+        }
+    }
+
+    #[test]
+    fn split_off_still_usable() {
+        let mut d: DefaultDeque<u32> = (0..200).collect();
+        let mut other = d.split_off(20);
+
+        d.push_back(20);
+        d.push_front(u32::MAX);
+        other.push_front(19);
+        other.push_back(200);
+        assert!(d.iter().copied().eq([u32::MAX].into_iter().chain(0..21)));
+        assert!(other.iter().copied().eq(19..201));
+    }
+
+    #[test]
+    #[should_panic]
+    fn split_off_out_of_bounds() {
+        let mut d: DefaultDeque<u32> = (0..10).collect();
+        d.split_off(11);
+    }
+
+    /// Asserts that `is_empty` agrees with `len`
+    #[allow(clippy::len_zero)]
+    fn assert_empty_consistent<T>(d: &DefaultDeque<T>) {
+        assert_eq!(d.is_empty(), d.len() == 0, "len is {}", d.len());
+    }
+
+    #[test]
+    fn is_empty_across_subarrays() {
+        // the subarrays for `u32` hold 64 elements, so these cross several boundaries
+        let mut d: DefaultDeque<u32> = DefaultDeque::new();
+        for n in 0..200 {
+            d.push_back(n);
+            assert_empty_consistent(&d);
+        }
+        while d.pop_front().is_some() {
+            assert_empty_consistent(&d);
+        }
+        assert!(d.is_empty());
+
+        for n in 0..200 {
+            d.push_front(n);
+            assert_empty_consistent(&d);
+        }
+        while d.pop_back().is_some() {
+            assert_empty_consistent(&d);
+        }
+        assert!(d.is_empty());
+
+        // alternate the ends so the deque drifts across boundaries while near-empty
+        for n in 0..300 {
+            if n % 3 == 0 {
+                d.push_front(n);
+            } else {
+                d.push_back(n);
+            }
+            assert_empty_consistent(&d);
+            if n % 2 == 0 {
+                d.pop_front();
+            } else {
+                d.pop_back();
+            }
+            assert_empty_consistent(&d);
+        }
+        assert!(d.is_empty());
+        assert_eq!(d.pop_front(), None);
+        assert_eq!(d.pop_back(), None);
+    }
+
+    #[test]
+    fn is_empty_after_drifting() {
+        // walk a single element far to the back, then far to the front
+        let mut d: DefaultDeque<u32> = DefaultDeque::new();
+        for n in 0..150 {
+            d.push_back(n);
+            assert_eq!(d.pop_front(), Some(n));
+            assert_empty_consistent(&d);
+        }
+        for n in 0..300 {
+            d.push_front(n);
+            assert_eq!(d.pop_back(), Some(n));
+            assert_empty_consistent(&d);
+        }
+        assert!(d.is_empty());
+    }
+
+    #[test]
+    fn contains() {
+        let d: DefaultDeque<u32> = (0..100).collect();
+        assert!(d.contains(&0));
+        assert!(d.contains(&99));
+        assert!(!d.contains(&100));
+    }
+
+    #[test]
+    fn position() {
+        // the subarrays for `u32` hold 64 elements, so this spans several of them
+        let mut d: DefaultDeque<u32> = (0..100).collect();
+        d.extend_front((100..200).rev());
+
+        assert_eq!(d.position(|n| *n == 150), Some(50));
+        assert_eq!(d.position(|n| *n == 70), Some(170));
+        assert_eq!(d.position(|n| *n > 1000), None);
+    }
+
+    #[test]
+    fn get() {
+        let mut d: DefaultDeque<u32> = (0..200).collect();
+        // move the front into the middle of a subarray
+        d.pop_front();
+        d.push_front(1000);
+        d.push_front(1001);
+
+        assert_eq!(d.get(0), Some(&1001));
+        assert_eq!(d.get(1), Some(&1000));
+        assert!((2..d.len()).all(|i| d.get(i) == Some(&(i as u32 - 1))));
+        assert_eq!(d.get(d.len()), None);
+
+        *d.get_mut(100).unwrap() = 5;
+        assert_eq!(d.iter().nth(100), Some(&5));
+        assert_eq!(d.get_mut(d.len()), None);
+    }
+
+    #[test]
+    fn binary_search() {
+        // sorted even numbers, starting partway through a subarray
+        let mut d: DefaultDeque<u32> = (1..150).map(|n| n * 2).collect();
+        d.push_front(0);
+
+        assert_eq!(d.binary_search(&0), Ok(0));
+        assert_eq!(d.binary_search(&128), Ok(64));
+        assert_eq!(d.binary_search(&298), Ok(149));
+
+        assert_eq!(d.binary_search(&129), Err(65));
+        assert_eq!(d.binary_search(&1000), Err(150));
+
+        let empty: DefaultDeque<u32> = DefaultDeque::new();
+        assert_eq!(empty.binary_search(&1), Err(0));
+    }
 }
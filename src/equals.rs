@@ -1,3 +1,4 @@
+use std::borrow::Borrow;
 use std::marker::PhantomData;
 
 /// A trait which takes two instances of something and returns true if they are equal
@@ -17,8 +18,92 @@ pub struct EqualTo<T> {
     _marker: PhantomData<T>,
 }
 
-impl<T: PartialEq> Equals<T> for EqualTo<T> {
-    fn equals(lhs: &T, rhs: &T) -> bool {
+impl<T: Borrow<Q>, Q: ?Sized + PartialEq> Equals<Q> for EqualTo<T> {
+    fn equals(lhs: &Q, rhs: &Q) -> bool {
         lhs == rhs
     }
 }
+
+/// A struct which compares two instances with a custom marker type, such as
+/// one that only compares certain fields. The marker is never stored, so the
+/// container's layout is unchanged
+pub struct EqualBy<T, F> {
+    _marker: PhantomData<(T, F)>,
+}
+
+impl<T, F: Equals<T>> Equals<T> for EqualBy<T, F> {
+    fn equals(lhs: &T, rhs: &T) -> bool {
+        F::equals(lhs, rhs)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::allocator::DefaultAllocator;
+    use crate::equals::{EqualBy, Equals};
+    use crate::hash::{DefaultHash, Hash};
+    use crate::hash_map::{DefaultHashMap, HashMap};
+
+    #[derive(PartialEq)]
+    struct Event {
+        id: u32,
+        timestamp: u64,
+    }
+
+    /// Hashes an event by its id only, to match `same_id`
+    struct EventHash;
+
+    impl Hash<Event> for EventHash {
+        fn hash(val: &Event) -> usize {
+            DefaultHash::<u32>::hash(&val.id)
+        }
+    }
+
+    /// Compares events by their id only, ignoring the timestamp
+    struct SameId;
+
+    impl Equals<Event> for SameId {
+        fn equals(lhs: &Event, rhs: &Event) -> bool {
+            lhs.id == rhs.id
+        }
+    }
+
+    type EventMap =
+        HashMap<Event, &'static str, DefaultAllocator, EventHash, EqualBy<Event, SameId>>;
+
+    #[test]
+    fn layout() {
+        assert_eq!(std::mem::size_of::<EqualBy<Event, SameId>>(), 0);
+        assert_eq!(
+            std::mem::size_of::<EventMap>(),
+            std::mem::size_of::<DefaultHashMap<u32, &'static str>>()
+        );
+    }
+
+    #[test]
+    fn equal_by_ignores_fields() {
+        let mut map: EventMap = unsafe { HashMap::new_in(DefaultAllocator::default()) };
+        map.insert(
+            Event {
+                id: 1,
+                timestamp: 100,
+            },
+            "first",
+        );
+
+        let later = Event {
+            id: 1,
+            timestamp: 200,
+        };
+        assert_eq!(map.get(&later), Some(&"first"));
+        assert_eq!(map.insert(later, "second"), Some("first"));
+        assert_eq!(map.len(), 1);
+        assert_eq!(
+            map.get(&Event {
+                id: 2,
+                timestamp: 100
+            }),
+            None
+        );
+    }
+}
@@ -3,6 +3,12 @@ use std::ffi::c_void;
 use std::ptr;
 use std::ptr::null_mut;
 
+#[cfg(debug_assertions)]
+thread_local! {
+    /// The number of allocations made by fixed vectors' overflow allocators on this thread
+    pub(crate) static OVERFLOW_COUNT: std::cell::Cell<usize> = const { std::cell::Cell::new(0) };
+}
+
 #[derive(Copy, Clone)]
 #[repr(C)]
 pub struct FixedVectorAllocator<A: Allocator> {
@@ -34,6 +40,9 @@ impl<A: Allocator + Default> Default for FixedVectorAllocator<A> {
 
 unsafe impl<A: Allocator> Allocator for FixedVectorAllocator<A> {
     fn allocate_raw_aligned(&mut self, n: usize, align: usize) -> *mut () {
+        // the fixed buffer is never allocated, so every allocation is an overflow
+        #[cfg(debug_assertions)]
+        OVERFLOW_COUNT.with(|count| count.set(count.get() + 1));
         self.overflow_allocator.allocate_raw_aligned(n, align)
     }
 
@@ -8,11 +8,20 @@ use std::fmt::Debug;
 use std::mem::{size_of, MaybeUninit};
 use std::ops::{Deref, DerefMut};
 use std::pin::Pin;
-use std::ptr::null_mut;
-use std::{mem, ptr};
+use std::ptr;
 
 mod allocator;
 
+/// Returns the number of times a fixed vector on this thread has spilled into,
+/// or grown within, its overflow allocator. Overflows are only counted in debug
+/// builds, so this always returns 0 in release builds
+pub fn overflow_count() -> usize {
+    #[cfg(debug_assertions)]
+    return allocator::OVERFLOW_COUNT.with(|count| count.get());
+    #[cfg(not(debug_assertions))]
+    0
+}
+
 /// Fixed vector with the default allocator.
 pub type DefaultFixedVector<T, const NODE_COUNT: usize> =
     FixedVector<T, NODE_COUNT, DefaultAllocator>;
@@ -64,29 +73,24 @@ impl<T: Sized, const NODE_COUNT: usize, A: Allocator + Default> FixedVector<T, N
 unsafe impl<T: Sized, const NODE_COUNT: usize, A: Allocator> MoveNew
     for FixedVector<T, NODE_COUNT, A>
 {
-    unsafe fn move_new(mut src: Pin<MoveRef<Self>>, this: Pin<&mut MaybeUninit<Self>>) {
-        let this = this.get_unchecked_mut().assume_init_mut();
-        let src = src.as_mut().get_unchecked_mut();
-        // Swap the allocator over
-        mem::swap(&mut this.base_vec.allocator, &mut src.base_vec.allocator);
-        if !src.has_overflowed() {
-            // We haven't overflowed, so we need to move the buffer
-            mem::swap(&mut this.buffer, &mut src.buffer);
-            // ... and re-init the base vec pointers point to it
+    unsafe fn move_new(src: Pin<MoveRef<Self>>, this: Pin<&mut MaybeUninit<Self>>) {
+        // the buffer is about to move, so record where the elements are first
+        let overflowed = src.has_overflowed();
+        let len = src.base_vec.len();
+        // take the source by value so it isn't dropped. `this` is uninitialized, so the whole
+        // fixed vector (allocator included) is moved over rather than swapped
+        let src = MoveRef::into_inner(Pin::into_inner_unchecked(src));
+        let this = this.get_unchecked_mut().write(src);
+        if !overflowed {
+            // the elements moved along with the buffer, so re-init the base vec pointers to it
             this.init_base_vec();
             // we have to fix the end pointer since it will be set to begin_ptr by init_base_vec
-            this.base_vec.end_ptr = (this.base_vec.begin_ptr as usize
-                + (src.base_vec.end_ptr as usize - src.base_vec.begin_ptr as usize))
-                as *mut T;
+            this.base_vec.end_ptr = this.base_vec.begin_ptr.add(len);
         } else {
-            // We have overflowed - we are not going to use `buffer` anymore so we might as well
-            // leave it uninit - so we only copy over the base vec pointers
-            this.base_vec.begin_ptr = src.base_vec.begin_ptr;
-            this.base_vec.end_ptr = src.base_vec.end_ptr;
-            this.base_vec.capacity_ptr = src.base_vec.capacity_ptr;
+            // we have overflowed, so the elements stay in the overflow allocation. the pool base
+            // must still point at our own buffer rather than the source's
+            this.base_vec.allocator.pool_begin = this.buffer[0].as_mut_ptr() as *mut c_void;
         }
-        // zero `src` `begin_ptr` so any allocated data will not be dropped (we pretend like we never allocated it)
-        src.base_vec.begin_ptr = null_mut();
     }
 }
 
@@ -100,6 +104,22 @@ impl<T: Sized, const NODE_COUNT: usize, A: Allocator> FixedVector<T, NODE_COUNT,
     pub fn has_overflowed(&self) -> bool {
         !ptr::eq(self.base_vec.begin_ptr, self.buffer[0].as_ptr())
     }
+
+    /// Returns the number of elements the inline buffer holds, which is the
+    /// user-supplied NodeCount parameter
+    pub fn inline_capacity(&self) -> usize {
+        NODE_COUNT
+    }
+
+    /// Returns the capacity of the overflow allocation, or 0 if the elements
+    /// are still stored inline
+    pub fn heap_capacity(&self) -> usize {
+        if self.has_overflowed() {
+            self.base_vec.capacity()
+        } else {
+            0
+        }
+    }
 }
 
 impl<T: Sized, const NODE_COUNT: usize, A: Allocator> AsRef<[T]> for FixedVector<T, NODE_COUNT, A> {
@@ -136,7 +156,8 @@ impl<T: Sized + Debug, const NODE_COUNT: usize, A: Allocator> DerefMut
 
 #[cfg(test)]
 mod test {
-    use crate::fixed_vector::DefaultFixedVector;
+    use crate::allocator::CountingAllocator;
+    use crate::fixed_vector::{overflow_count, DefaultFixedVector, FixedVector};
     use moveit::{moveit, MoveNew};
     use std::mem::MaybeUninit;
     use std::pin::Pin;
@@ -171,6 +192,48 @@ mod test {
         assert_eq!(v.as_slice()[11], 11);
     }
 
+    #[test]
+    fn capacities() {
+        moveit! {
+            let mut v = unsafe { DefaultFixedVector::<u32, 10>::new() };
+        };
+        assert_eq!(v.inline_capacity(), 10);
+        assert_eq!(v.heap_capacity(), 0);
+
+        for i in 0..10 {
+            v.push(i);
+        }
+        assert!(!v.has_overflowed());
+        assert_eq!(v.heap_capacity(), 0);
+
+        v.push(10);
+        assert!(v.has_overflowed());
+        assert_eq!(v.inline_capacity(), 10);
+        assert!(v.heap_capacity() >= 11);
+        assert_eq!(v.heap_capacity(), v.capacity());
+    }
+
+    #[test]
+    fn overflow_count_grows() {
+        let allocator = CountingAllocator::default();
+        moveit! {
+            let mut v = unsafe { FixedVector::<u32, 2, CountingAllocator>::new_in(allocator.clone()) };
+        };
+        let overflows = overflow_count();
+        v.push(0);
+        v.push(1);
+        assert_eq!(allocator.allocations(), 0);
+
+        v.push(2);
+        assert_eq!(allocator.allocations(), 1);
+        // the count is per thread, and only kept in debug builds
+        if cfg!(debug_assertions) {
+            assert_eq!(overflow_count(), overflows + 1);
+        } else {
+            assert_eq!(overflow_count(), 0);
+        }
+    }
+
     #[test]
     fn iter() {
         moveit! {
@@ -213,4 +276,28 @@ mod test {
         assert!(target.has_overflowed());
         assert_eq!(target.as_slice()[11], 11);
     }
+
+    #[test]
+    fn move_then_overflow() {
+        let allocator = CountingAllocator::default();
+        moveit! {
+            let mut v = unsafe { FixedVector::<u32, 10, _>::new_in(allocator.clone()) };
+        };
+        v.push(1);
+        v.push(2);
+        let mut target = MaybeUninit::<FixedVector<u32, 10, CountingAllocator>>::uninit();
+        unsafe { MoveNew::move_new(v, Pin::new_unchecked(&mut target)) };
+        let target_ref = unsafe { target.assume_init_mut() };
+        assert_eq!(allocator.allocations(), 0);
+
+        for i in 3..=12 {
+            target_ref.push(i);
+        }
+        assert!(target_ref.has_overflowed());
+        assert!(target_ref.iter().copied().eq(1..=12));
+        assert_eq!(allocator.allocations(), 1);
+
+        unsafe { target.assume_init_drop() };
+        assert_eq!(allocator.live_bytes(), 0);
+    }
 }
@@ -1,3 +1,5 @@
+#[cfg(test)]
+use std::cell::Cell;
 use std::ffi::{c_char, CStr};
 use std::marker::PhantomData;
 
@@ -7,13 +9,71 @@ pub trait Hash<T: ?Sized> {
     fn hash(val: &T) -> usize;
 }
 
+/// Defines a hash function for a borrowed form `Q` of a hasher's key, which
+/// must agree with the hash of the key itself. Hash tables use it to look up
+/// keys by their borrowed form, and every hasher can hash its own key type
+pub trait BorrowHash<Q: ?Sized> {
+    fn hash_borrowed(val: &Q) -> usize;
+}
+
+impl<Q: ?Sized, H: Hash<Q>> BorrowHash<Q> for H {
+    fn hash_borrowed(val: &Q) -> usize {
+        H::hash(val)
+    }
+}
+
+/// Defines whether hash table nodes store the hash of their key,
+/// mirroring EASTL's `bCacheHashCode` parameter
+pub trait HashCode {
+    /// Creates the stored hash code from the hash of a key
+    ///
+    /// # Arguments
+    ///
+    /// `hash`: The hash of the key
+    fn new(hash: usize) -> Self;
+
+    /// Returns the hash of the key, if it was stored
+    fn get(&self) -> Option<usize>;
+}
+
+/// Nodes don't store the hash of their key, so the hash is recomputed
+/// when re-hashing. This is EASTL's default, and keeps nodes smaller
+#[derive(Clone, Copy, Debug)]
+pub struct NoHashCode;
+
+impl HashCode for NoHashCode {
+    fn new(_hash: usize) -> Self {
+        Self
+    }
+
+    fn get(&self) -> Option<usize> {
+        None
+    }
+}
+
+/// Nodes store the hash of their key after the next pointer, so
+/// re-hashing never calls the hash function. Worth it for expensive hashes
+#[derive(Clone, Copy, Debug)]
+#[repr(transparent)]
+pub struct CachedHashCode(usize);
+
+impl HashCode for CachedHashCode {
+    fn new(hash: usize) -> Self {
+        Self(hash)
+    }
+
+    fn get(&self) -> Option<usize> {
+        Some(self.0)
+    }
+}
+
 /// The default hash struct implemented for basic types
 pub struct DefaultHash<T: ?Sized> {
     _ignore_type: PhantomData<T>,
 }
 
-/// Default implementations
-/// TODO: Make these more loosely typed
+// Default implementations
+// TODO: Make these more loosely typed
 
 impl Hash<u8> for DefaultHash<u8> {
     fn hash(val: &u8) -> usize {
@@ -106,6 +166,40 @@ fn fnv1<S: AsRef<str>>(str: S) -> usize {
     res as usize
 }
 
+/// The FNV1a hash function
+///
+/// # Arguments
+///
+/// `str`: The string to hash
+fn fnv1a<S: AsRef<str>>(str: S) -> usize {
+    let mut res: u32 = 2166136261;
+    str.as_ref()
+        .bytes()
+        .for_each(|c| res = (res ^ (c as u32)).wrapping_mul(16777619));
+    res as usize
+}
+
+/// A string hash functor using FNV1a, which can be used as the `H` parameter
+/// of string-keyed hash maps and sets in place of `DefaultHash`. `DefaultHash`
+/// uses FNV1 like EASTL, which multiplies before mixing in each byte, so the
+/// last byte only ever affects the low bits of the hash. FNV1a mixes in each
+/// byte before multiplying, spreading every byte across the whole hash. The
+/// hashes differ from EASTL's, so tables using this can't be shared with C++
+/// code expecting the default hash
+pub struct Fnv1a;
+
+impl Hash<str> for Fnv1a {
+    fn hash(val: &str) -> usize {
+        fnv1a(val)
+    }
+}
+
+impl Hash<&str> for Fnv1a {
+    fn hash(val: &&str) -> usize {
+        fnv1a(val)
+    }
+}
+
 impl Hash<str> for DefaultHash<str> {
     fn hash(val: &str) -> usize {
         fnv1(val)
@@ -124,11 +218,45 @@ impl Hash<*const c_char> for DefaultHash<*const c_char> {
     }
 }
 
+#[cfg(test)]
+thread_local! {
+    static HASHES: Cell<usize> = const { Cell::new(0) };
+}
+
+/// A hash which keeps track of the number of times it was called on the
+/// current thread. `u32` keys hash to themselves
+#[cfg(test)]
+pub(crate) struct CountingHash;
+
+#[cfg(test)]
+impl CountingHash {
+    /// The number of hashes computed on this thread since the last reset
+    pub(crate) fn hashes() -> usize {
+        HASHES.with(Cell::get)
+    }
+
+    /// Resets the number of hashes computed on this thread
+    pub(crate) fn reset() {
+        HASHES.with(|hashes| hashes.set(0))
+    }
+}
+
+#[cfg(test)]
+impl Hash<u32> for CountingHash {
+    fn hash(val: &u32) -> usize {
+        HASHES.with(|hashes| hashes.set(hashes.get() + 1));
+        *val as usize
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::DefaultHash;
-    use super::Hash;
+    use super::{Fnv1a, Hash};
     use crate::allocator::DefaultAllocator;
+    use crate::hash_map::HashMap;
+    use crate::string::DefaultString;
+    use std::collections::BTreeSet;
     use std::ffi::{c_char, CString};
 
     #[test]
@@ -148,4 +276,46 @@ mod test {
             556965705
         );
     }
+
+    #[test]
+    fn fnv1a() {
+        assert_eq!(Fnv1a::hash(""), 2166136261);
+        assert_eq!(Fnv1a::hash("Test"), 805092869);
+        assert_eq!(
+            Fnv1a::hash(&DefaultString::from("Test")),
+            Fnv1a::hash("Test")
+        );
+    }
+
+    #[test]
+    fn fnv1a_avalanche() {
+        // keys which only differ in their last byte
+        let keys: Vec<std::string::String> = ('a'..='z').map(|c| format!("key_{c}")).collect();
+        let high_bits = |hash: usize| hash >> 16;
+
+        // FNV1 only mixes the last byte into the low bits
+        let fnv1: BTreeSet<usize> = keys
+            .iter()
+            .map(|key| high_bits(DefaultHash::<str>::hash(key)))
+            .collect();
+        assert_eq!(fnv1.len(), 1);
+
+        let fnv1a: BTreeSet<usize> = keys
+            .iter()
+            .map(|key| high_bits(Fnv1a::hash(key.as_str())))
+            .collect();
+        assert_eq!(fnv1a.len(), keys.len());
+    }
+
+    #[test]
+    fn fnv1a_hash_map() {
+        let mut hm: HashMap<DefaultString, usize, DefaultAllocator, Fnv1a> =
+            unsafe { HashMap::new_in(DefaultAllocator::default()) };
+        for n in 0..100 {
+            hm.insert(DefaultString::from(format!("key_{n}").as_str()), n);
+        }
+        assert_eq!(hm.len(), 100);
+        assert!((0..100).all(|n| hm.get(format!("key_{n}").as_str()) == Some(&n)));
+        assert_eq!(hm.get("key_100"), None);
+    }
 }
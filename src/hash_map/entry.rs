@@ -1,14 +1,23 @@
 use crate::allocator::Allocator;
 use crate::equals::Equals;
-use crate::hash::Hash;
+use crate::hash::{Hash, HashCode, NoHashCode};
 use crate::internal::hash_table;
+use std::fmt::{Debug, Display, Formatter};
 
 /// An entry in a hash map.
-pub struct Entry<'a, K: PartialEq, V, A: Allocator, H: Hash<K>, E: Equals<K>>(
-    hash_table::entry::Entry<'a, K, V, A, H, E>,
-);
+pub struct Entry<
+    'a,
+    K: PartialEq,
+    V,
+    A: Allocator,
+    H: Hash<K>,
+    E: Equals<K>,
+    C: HashCode = NoHashCode,
+>(hash_table::entry::Entry<'a, K, V, A, H, E, C>);
 
-impl<'a, K: PartialEq, V, A: Allocator, H: Hash<K>, E: Equals<K>> Entry<'a, K, V, A, H, E> {
+impl<'a, K: PartialEq, V, A: Allocator, H: Hash<K>, E: Equals<K>, C: HashCode>
+    Entry<'a, K, V, A, H, E, C>
+{
     /// Provides in-place mutable access to the value.
     ///
     /// # Arguments
@@ -35,12 +44,256 @@ impl<'a, K: PartialEq, V, A: Allocator, H: Hash<K>, E: Equals<K>> Entry<'a, K, V
     pub fn or_insert_with<F: FnOnce() -> V>(self, default: F) -> &'a mut V {
         self.0.or_insert_with(default)
     }
+
+    /// Fetches the value stored in the entry, or inserts a default key
+    /// produced from the entry's key.
+    ///
+    /// # Arguments
+    ///
+    /// `default`: A function producing a default value from the key.
+    pub fn or_insert_with_key<F: FnOnce(&K) -> V>(self, default: F) -> &'a mut V {
+        self.0.or_insert_with_key(default)
+    }
+
+    /// Fetches the value stored in the entry, or inserts the default value.
+    pub fn or_default(self) -> &'a mut V
+    where
+        V: Default,
+    {
+        self.0.or_insert_with(V::default)
+    }
+}
+
+impl<'a, K: PartialEq, V, A: Allocator, H: Hash<K>, E: Equals<K>, C: HashCode>
+    From<hash_table::entry::Entry<'a, K, V, A, H, E, C>> for Entry<'a, K, V, A, H, E, C>
+{
+    fn from(value: hash_table::entry::Entry<'a, K, V, A, H, E, C>) -> Self {
+        Self(value)
+    }
+}
+
+/// An entry in a hash map, looked up by a borrowed key.
+pub struct EntryRef<
+    'a,
+    'b,
+    K: PartialEq,
+    Q: ?Sized,
+    V,
+    A: Allocator,
+    H: Hash<K>,
+    E: Equals<K>,
+    C: HashCode = NoHashCode,
+>(hash_table::entry::EntryRef<'a, 'b, K, Q, V, A, H, E, C>);
+
+impl<'a, 'b, K: PartialEq, Q: ?Sized, V, A: Allocator, H: Hash<K>, E: Equals<K>, C: HashCode>
+    EntryRef<'a, 'b, K, Q, V, A, H, E, C>
+{
+    /// Provides in-place mutable access to the value.
+    ///
+    /// # Arguments
+    ///
+    /// `f`: A function taking a mutable reference to the value.
+    pub fn and_modify<F: Fn(&mut V)>(self, f: F) -> Self {
+        self.0.and_modify(f).into()
+    }
+
+    /// Fetches the value stored in the entry, or inserts a default key.
+    ///
+    /// # Arguments
+    ///
+    /// `default`: The default value.
+    pub fn or_insert(self, default: V) -> &'a mut V
+    where
+        K: From<&'b Q>,
+    {
+        self.0.or_insert(default)
+    }
+
+    /// Fetches the value stored in the entry, or inserts a default key.
+    ///
+    /// # Arguments
+    ///
+    /// `default`: A function producing a default value.
+    pub fn or_insert_with<F: FnOnce() -> V>(self, default: F) -> &'a mut V
+    where
+        K: From<&'b Q>,
+    {
+        self.0.or_insert_with(default)
+    }
+
+    /// Fetches the value stored in the entry, or inserts a default key,
+    /// creating the owned key with a function.
+    ///
+    /// # Arguments
+    ///
+    /// `to_owned`: A function producing the owned key from the borrowed key.
+    ///
+    /// `default`: A function producing a default value.
+    pub fn or_insert_with_owned_key<F: FnOnce(&'b Q) -> K, G: FnOnce() -> V>(
+        self,
+        to_owned: F,
+        default: G,
+    ) -> &'a mut V {
+        self.0.or_insert_with_owned_key(to_owned, default)
+    }
+}
+
+impl<'a, 'b, K: PartialEq, Q: ?Sized, V, A: Allocator, H: Hash<K>, E: Equals<K>, C: HashCode>
+    From<hash_table::entry::EntryRef<'a, 'b, K, Q, V, A, H, E, C>>
+    for EntryRef<'a, 'b, K, Q, V, A, H, E, C>
+{
+    fn from(value: hash_table::entry::EntryRef<'a, 'b, K, Q, V, A, H, E, C>) -> Self {
+        Self(value)
+    }
+}
+
+/// A builder to look up pairs in a hash map by a precomputed hash.
+pub type RawEntryBuilder<'a, K, V, A, H, E, C = NoHashCode> =
+    hash_table::entry::RawEntryBuilder<'a, K, V, A, H, E, C>;
+
+/// A builder to look up or insert entries in a hash map by a precomputed hash.
+pub struct RawEntryBuilderMut<
+    'a,
+    K: PartialEq,
+    V,
+    A: Allocator,
+    H: Hash<K>,
+    E: Equals<K>,
+    C: HashCode = NoHashCode,
+>(hash_table::entry::RawEntryBuilderMut<'a, K, V, A, H, E, C>);
+
+impl<'a, K: PartialEq, V, A: Allocator, H: Hash<K>, E: Equals<K>, C: HashCode>
+    RawEntryBuilderMut<'a, K, V, A, H, E, C>
+{
+    /// Fetches the entry whose key matches.
+    ///
+    /// # Arguments
+    ///
+    /// `hash`: The hash of the key, which must equal the map's hash of it.
+    ///
+    /// `is_match`: A function returning true for the matching key.
+    #[allow(clippy::wrong_self_convention)]
+    pub fn from_hash<F: FnMut(&K) -> bool>(
+        self,
+        hash: usize,
+        is_match: F,
+    ) -> RawEntryMut<'a, K, V, A, H, E, C> {
+        self.0.from_hash(hash, is_match).into()
+    }
+}
+
+impl<'a, K: PartialEq, V, A: Allocator, H: Hash<K>, E: Equals<K>, C: HashCode>
+    From<hash_table::entry::RawEntryBuilderMut<'a, K, V, A, H, E, C>>
+    for RawEntryBuilderMut<'a, K, V, A, H, E, C>
+{
+    fn from(value: hash_table::entry::RawEntryBuilderMut<'a, K, V, A, H, E, C>) -> Self {
+        Self(value)
+    }
+}
+
+/// An entry in a hash map, looked up by a precomputed hash.
+pub struct RawEntryMut<
+    'a,
+    K: PartialEq,
+    V,
+    A: Allocator,
+    H: Hash<K>,
+    E: Equals<K>,
+    C: HashCode = NoHashCode,
+>(hash_table::entry::RawEntryMut<'a, K, V, A, H, E, C>);
+
+impl<'a, K: PartialEq, V, A: Allocator, H: Hash<K>, E: Equals<K>, C: HashCode>
+    RawEntryMut<'a, K, V, A, H, E, C>
+{
+    /// Provides in-place mutable access to the value.
+    ///
+    /// # Arguments
+    ///
+    /// `f`: A function taking the key and a mutable reference to the value.
+    pub fn and_modify<F: FnOnce(&K, &mut V)>(self, f: F) -> Self {
+        self.0.and_modify(f).into()
+    }
+
+    /// Returns the occupied entry if a pair matched, or the vacant entry otherwise.
+    #[allow(clippy::type_complexity)]
+    pub fn occupied(
+        self,
+    ) -> Result<RawOccupiedEntryMut<'a, K, V, A, H, E, C>, RawVacantEntryMut<'a, K, V, A, H, E, C>>
+    {
+        match self.0 {
+            hash_table::entry::RawEntryMut::Occupied(entry) => Ok(entry),
+            hash_table::entry::RawEntryMut::Vacant(entry) => Err(entry),
+        }
+    }
+
+    /// Fetches the pair stored in the entry, or inserts a default pair.
+    ///
+    /// # Arguments
+    ///
+    /// `key`: The default key, which must hash to the entry's hash.
+    ///
+    /// `value`: The default value.
+    pub fn or_insert(self, key: K, value: V) -> (&'a K, &'a mut V) {
+        self.0.or_insert(key, value)
+    }
+
+    /// Fetches the pair stored in the entry, or inserts a default pair.
+    ///
+    /// # Arguments
+    ///
+    /// `default`: A function producing the default pair, whose key must hash
+    /// to the entry's hash.
+    pub fn or_insert_with<F: FnOnce() -> (K, V)>(self, default: F) -> (&'a K, &'a mut V) {
+        self.0.or_insert_with(default)
+    }
 }
 
-impl<'a, K: PartialEq, V, A: Allocator, H: Hash<K>, E: Equals<K>>
-    From<hash_table::entry::Entry<'a, K, V, A, H, E>> for Entry<'a, K, V, A, H, E>
+impl<'a, K: PartialEq, V, A: Allocator, H: Hash<K>, E: Equals<K>, C: HashCode>
+    From<hash_table::entry::RawEntryMut<'a, K, V, A, H, E, C>>
+    for RawEntryMut<'a, K, V, A, H, E, C>
 {
-    fn from(value: hash_table::entry::Entry<'a, K, V, A, H, E>) -> Self {
+    fn from(value: hash_table::entry::RawEntryMut<'a, K, V, A, H, E, C>) -> Self {
         Self(value)
     }
 }
+
+/// An occupied entry in a hash map, looked up by a precomputed hash.
+pub type RawOccupiedEntryMut<'a, K, V, A, H, E, C = NoHashCode> =
+    hash_table::entry::RawOccupiedEntryMut<'a, K, V, A, H, E, C>;
+
+/// A vacant entry in a hash map, looked up by a precomputed hash.
+pub type RawVacantEntryMut<'a, K, V, A, H, E, C = NoHashCode> =
+    hash_table::entry::RawVacantEntryMut<'a, K, V, A, H, E, C>;
+
+/// The error returned by `HashMap::try_insert` when the key is already present.
+/// The map is left unchanged.
+pub struct OccupiedError<'a, K, V> {
+    /// The key already present in the map.
+    pub key: &'a K,
+    /// The value already present in the map.
+    pub existing: &'a mut V,
+    /// The value which was not inserted.
+    pub value: V,
+}
+
+impl<'a, K: Debug, V: Debug> Debug for OccupiedError<'a, K, V> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("OccupiedError")
+            .field("key", &self.key)
+            .field("existing", &self.existing)
+            .field("value", &self.value)
+            .finish()
+    }
+}
+
+impl<'a, K: Debug, V: Debug> Display for OccupiedError<'a, K, V> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "failed to insert {:?}, key {:?} already exists with value {:?}",
+            self.value, self.key, self.existing
+        )
+    }
+}
+
+impl<'a, K: Debug, V: Debug> std::error::Error for OccupiedError<'a, K, V> {}
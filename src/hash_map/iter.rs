@@ -1,3 +1,5 @@
+use crate::hash::NoHashCode;
+
 /// An iterator that produces key-value pairs
 /// in a hash map in an unspecified order. This
 /// is not binary compatible with C++.
@@ -7,7 +9,7 @@
 /// It is unspecified whether or not an element
 /// inserted after an iterator was created will
 /// be yielded by the iterator
-pub type Iter<'a, K, V> = crate::internal::hash_table::iter::Iter<'a, K, V>;
+pub type Iter<'a, K, V, C = NoHashCode> = crate::internal::hash_table::iter::Iter<'a, K, V, C>;
 
 /// An iterator that produces key-value pairs
 /// in a hash map in an unspecified order. The
@@ -22,4 +24,5 @@ pub type Iter<'a, K, V> = crate::internal::hash_table::iter::Iter<'a, K, V>;
 /// It is unspecified whether or not an element
 /// inserted after an iterator was created will
 /// be yielded by the iterator
-pub type IterMut<'a, K, V> = crate::internal::hash_table::iter::IterMut<'a, K, V>;
+pub type IterMut<'a, K, V, C = NoHashCode> =
+    crate::internal::hash_table::iter::IterMut<'a, K, V, C>;
@@ -1,11 +1,12 @@
 use crate::allocator::DefaultAllocator;
 use crate::equals::{EqualTo, Equals};
-use crate::hash_map::entry::Entry;
+use crate::hash_map::entry::{Entry, EntryRef, OccupiedError, RawEntryBuilder, RawEntryBuilderMut};
 use crate::{
     allocator::Allocator,
-    hash::{DefaultHash, Hash},
-    internal::hash_table::HashTable,
+    hash::{BorrowHash, DefaultHash, Hash, HashCode, NoHashCode},
+    internal::hash_table::{self, node::Node, HashTable},
 };
+use std::borrow::Borrow;
 use std::fmt::{Debug, Formatter};
 
 use self::iter::{Iter, IterMut};
@@ -17,7 +18,9 @@ pub mod iter;
 pub type DefaultHashMap<K, V, H = DefaultHash<K>, E = EqualTo<K>> =
     HashMap<K, V, DefaultAllocator, H, E>;
 
-/// A hash map that can store and fetch values from a key in O(1) time
+/// A hash map that can store and fetch values from a key in O(1) time.
+/// `C` decides whether nodes store the hash of their key - `CachedHashCode`
+/// saves calling expensive hashes again when re-hashing
 #[repr(C)]
 pub struct HashMap<
     K: PartialEq,
@@ -25,8 +28,9 @@ pub struct HashMap<
     A: Allocator,
     H: Hash<K> = DefaultHash<K>,
     E: Equals<K> = EqualTo<K>,
+    C: HashCode = NoHashCode,
 > {
-    hash_table: HashTable<K, V, A, H, E>,
+    hash_table: HashTable<K, V, A, H, E, C>,
 }
 
 impl<K: PartialEq, V, A: Allocator + Default> HashMap<K, V, A, DefaultHash<K>, EqualTo<K>>
@@ -39,9 +43,54 @@ where
             hash_table: HashTable::new(),
         }
     }
+
+    /// Creates a new empty hash map with at least the given number of
+    /// buckets, rounded up to the next prime. Unlike reserving, this counts
+    /// buckets rather than elements
+    ///
+    /// # Arguments
+    ///
+    /// `bucket_count`: The minimum number of buckets
+    pub fn with_bucket_count(bucket_count: usize) -> Self {
+        Self {
+            hash_table: HashTable::with_bucket_count(bucket_count),
+        }
+    }
 }
 
-impl<K: PartialEq, V, A: Allocator, H: Hash<K>, E: Equals<K>> HashMap<K, V, A, H, E> {
+impl<K: PartialEq, V, A: Allocator, H: Hash<K>, E: Equals<K>, C: HashCode>
+    HashMap<K, V, A, H, E, C>
+{
+    /// Returns an iterator over the key-value pairs in a single bucket, which
+    /// is useful for checking how well a hash distributes keys
+    ///
+    /// # Arguments
+    ///
+    /// `index`: The index of the bucket, which must be less than `bucket_count()`
+    pub fn bucket_iter(&self, index: usize) -> impl Iterator<Item = (&K, &V)> {
+        self.hash_table.bucket_iter(index)
+    }
+
+    /// Returns the number of entries in the bucket which a key maps to
+    ///
+    /// # Arguments
+    ///
+    /// `key`: The key used to pick the bucket
+    pub fn bucket_len(&self, key: &K) -> usize {
+        self.hash_table.bucket_len(key)
+    }
+
+    /// Returns the number of buckets in the hash map
+    pub fn bucket_count(&self) -> usize {
+        self.hash_table.bucket_count()
+    }
+
+    /// Returns the number of key-value pairs the hash map can hold before it
+    /// needs to rehash
+    pub fn capacity(&self) -> usize {
+        self.hash_table.capacity()
+    }
+
     /// Clears the hash map, removing all key-value pairs
     pub fn clear(&mut self) {
         self.hash_table.clear()
@@ -52,23 +101,55 @@ impl<K: PartialEq, V, A: Allocator, H: Hash<K>, E: Equals<K>> HashMap<K, V, A, H
     /// # Arguments
     ///
     /// `key`: The key to search for
-    pub fn contains_key(&self, key: &K) -> bool {
+    pub fn contains_key<Q: ?Sized>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        H: BorrowHash<Q>,
+        E: Equals<Q>,
+    {
         self.hash_table.contains_key(key)
     }
 
     /// Gets the given key’s corresponding entry in the map for in-place manipulation.
     ///
     /// `key`: The key.
-    pub fn entry(&mut self, key: K) -> Entry<K, V, A, H, E> {
+    pub fn entry(&mut self, key: K) -> Entry<'_, K, V, A, H, E, C> {
         self.hash_table.entry(key).into()
     }
 
+    /// Gets the given key’s corresponding entry in the map for in-place manipulation,
+    /// only creating an owned key if the entry is vacant and a value is inserted.
+    ///
+    /// `key`: The borrowed key.
+    pub fn entry_ref<'b, Q: ?Sized>(&mut self, key: &'b Q) -> EntryRef<'_, 'b, K, Q, V, A, H, E, C>
+    where
+        K: Borrow<Q>,
+        H: BorrowHash<Q>,
+        E: Equals<Q>,
+    {
+        self.hash_table.entry_ref(key).into()
+    }
+
+    /// Calls `f` with every key and mutable value in the hash map
+    ///
+    /// # Arguments
+    ///
+    /// `f`: The function to call with each pair
+    pub fn for_each_mut<F: FnMut(&K, &mut V)>(&mut self, f: F) {
+        self.hash_table.for_each_mut(f)
+    }
+
     /// Fetches the associated value for a key
     ///
     /// # Arguments
     ///
     /// `key`: The key to search for
-    pub fn get(&self, key: &K) -> Option<&V> {
+    pub fn get<Q: ?Sized>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        H: BorrowHash<Q>,
+        E: Equals<Q>,
+    {
         self.hash_table.get(key).map(|(_, v)| v)
     }
 
@@ -77,10 +158,71 @@ impl<K: PartialEq, V, A: Allocator, H: Hash<K>, E: Equals<K>> HashMap<K, V, A, H
     /// # Arguments
     ///
     /// `key`: The key to search for
-    pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+    pub fn get_mut<Q: ?Sized>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        H: BorrowHash<Q>,
+        E: Equals<Q>,
+    {
         self.hash_table.get_mut(key)
     }
 
+    /// Fetches a pointer to the node holding a key, for interop with C++
+    /// code holding iterators into the map. Nodes never move, so the pointer
+    /// stays valid until the pair is removed
+    ///
+    /// # Arguments
+    ///
+    /// `key`: The key to search for
+    pub fn get_node_ptr<Q: ?Sized>(&self, key: &Q) -> Option<*const Node<K, V, C>>
+    where
+        K: Borrow<Q>,
+        H: BorrowHash<Q>,
+        E: Equals<Q>,
+    {
+        self.hash_table.get_node_ptr(key)
+    }
+
+    /// Fetches a mutable pointer to the node holding a key, for interop with
+    /// C++ code holding iterators into the map. Nodes never move, so the
+    /// pointer stays valid until the pair is removed
+    ///
+    /// # Arguments
+    ///
+    /// `key`: The key to search for
+    pub fn get_node_ptr_mut<Q: ?Sized>(&mut self, key: &Q) -> Option<*mut Node<K, V, C>>
+    where
+        K: Borrow<Q>,
+        H: BorrowHash<Q>,
+        E: Equals<Q>,
+    {
+        self.hash_table.get_node_ptr_mut(key)
+    }
+
+    /// Fetches mutable references to the values associated with several
+    /// keys at once. Returns `None` if any of the keys are missing, or if
+    /// any two of the keys are equal
+    ///
+    /// # Arguments
+    ///
+    /// `keys`: The keys to search for
+    pub fn get_many_mut<const N: usize>(&mut self, keys: [&K; N]) -> Option<[&mut V; N]> {
+        self.hash_table.get_many_mut(keys)
+    }
+
+    /// Fetches the value indexed by the key, inserting the default value
+    /// first if the key isn't present
+    ///
+    /// # Arguments
+    ///
+    /// `key`: The key to index the pair
+    pub fn get_or_insert_default(&mut self, key: K) -> &mut V
+    where
+        V: Default,
+    {
+        self.hash_table.get_or_insert_default(key)
+    }
+
     /// Inserts the key-value pair into the hash map, returning the old value in the map
     ///
     /// # Arguments
@@ -99,14 +241,14 @@ impl<K: PartialEq, V, A: Allocator, H: Hash<K>, E: Equals<K>> HashMap<K, V, A, H
 
     /// Returns an iterator over the hash map's
     /// key-value pairs
-    pub fn iter(&self) -> Iter<K, V> {
+    pub fn iter(&self) -> Iter<'_, K, V, C> {
         self.hash_table.iter()
     }
 
     /// Returns an iterator over the hash map's
     /// key-value pairs, where the values are
     /// mutable
-    pub fn iter_mut(&mut self) -> IterMut<K, V> {
+    pub fn iter_mut(&mut self) -> IterMut<'_, K, V, C> {
         self.hash_table.iter_mut()
     }
 
@@ -115,6 +257,11 @@ impl<K: PartialEq, V, A: Allocator, H: Hash<K>, E: Equals<K>> HashMap<K, V, A, H
         self.hash_table.len()
     }
 
+    /// Returns the average number of key-value pairs per bucket
+    pub fn load_factor(&self) -> f32 {
+        self.hash_table.load_factor()
+    }
+
     /// Creates a hash map backed by an allocator
     ///
     /// # Arguments
@@ -130,21 +277,120 @@ impl<K: PartialEq, V, A: Allocator, H: Hash<K>, E: Equals<K>> HashMap<K, V, A, H
         }
     }
 
+    /// Creates a builder to look up pairs by a precomputed hash and a
+    /// key-matching function
+    pub fn raw_entry(&self) -> RawEntryBuilder<'_, K, V, A, H, E, C> {
+        self.hash_table.raw_entry()
+    }
+
+    /// Creates a builder to look up or insert entries by a precomputed hash
+    /// and a key-matching function. The hash must match the map's hash of the key
+    pub fn raw_entry_mut(&mut self) -> RawEntryBuilderMut<'_, K, V, A, H, E, C> {
+        self.hash_table.raw_entry_mut().into()
+    }
+
     /// Removes a key-value pair from the hash map,
     /// returning the element if it was found
-    pub fn remove(&mut self, key: &K) -> Option<V> {
+    pub fn remove<Q: ?Sized>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        H: BorrowHash<Q>,
+        E: Equals<Q>,
+    {
         self.hash_table.remove(key)
     }
 
+    /// Reserves space so that at least `additional` more elements can be
+    /// inserted without re-hashing
+    ///
+    /// # Arguments
+    ///
+    /// `additional`: The number of additional elements
+    ///
+    /// # Panics
+    ///
+    /// Panics if the total number of elements would overflow a `u32`
+    pub fn reserve(&mut self, additional: usize) {
+        self.hash_table.reserve(additional)
+    }
+
+    /// Inserts a key-value pair only if the key is not already present,
+    /// returning a mutable reference to the inserted value. If the key is
+    /// present, the map is left unchanged and the value is returned in the error
+    ///
+    /// # Arguments
+    ///
+    /// `key`: The key to insert
+    ///
+    /// `value`: The value to insert
+    pub fn try_insert(&mut self, key: K, value: V) -> Result<&mut V, OccupiedError<'_, K, V>> {
+        match self.hash_table.entry(key) {
+            hash_table::entry::Entry::Occupied(node) => Err(OccupiedError {
+                key: &node.key,
+                existing: &mut node.val,
+                value,
+            }),
+            hash_table::entry::Entry::Vacant(entry) => Ok(entry.insert(value)),
+        }
+    }
+
+    /// Removes a key-value pair from the hash map, returning the value if it
+    /// was found. Unlike `remove_entry`, the key is dropped in place rather
+    /// than being moved out of the map
+    pub fn remove_value<Q: ?Sized>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        H: BorrowHash<Q>,
+        E: Equals<Q>,
+    {
+        self.hash_table.remove_value(key)
+    }
+
     /// Removes a key-value pair from the hash map,
     /// returning the pair if it was found
-    pub fn remove_entry(&mut self, key: &K) -> Option<(K, V)> {
+    pub fn remove_entry<Q: ?Sized>(&mut self, key: &Q) -> Option<(K, V)>
+    where
+        K: Borrow<Q>,
+        H: BorrowHash<Q>,
+        E: Equals<Q>,
+    {
         self.hash_table.remove_entry(key)
     }
 }
 
-impl<K: Debug + PartialEq, V: Debug, A: Allocator, H: Hash<K>, E: Equals<K>> Debug
-    for HashMap<K, V, A, H, E>
+impl<K: Debug + Ord, V: Debug, A: Allocator, H: Hash<K>, E: Equals<K>, C: HashCode>
+    HashMap<K, V, A, H, E, C>
+{
+    /// Returns a formatter which renders the entries sorted by key, unlike
+    /// `Debug` which uses the unspecified bucket order. Useful for snapshot tests
+    pub fn debug_ordered(&self) -> impl Debug + '_ {
+        let mut entries = self.iter().collect::<Vec<_>>();
+        entries.sort_unstable_by_key(|(k, _)| *k);
+        OrderedDebug { entries }
+    }
+}
+
+/// Formats hash map entries in a fixed order
+struct OrderedDebug<'a, K, V> {
+    entries: Vec<(&'a K, &'a V)>,
+}
+
+impl<K: Debug, V: Debug> Debug for OrderedDebug<'_, K, V> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{{{}}}",
+            self.entries
+                .iter()
+                .map(|(k, v)| format!("{k:?}: {v:?}"))
+                .collect::<Vec<String>>()
+                .join(",")
+        )
+    }
+}
+
+impl<K: Debug + PartialEq, V: Debug, A: Allocator, H: Hash<K>, E: Equals<K>, C: HashCode> Debug
+    for HashMap<K, V, A, H, E, C>
 {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(
@@ -158,6 +404,26 @@ impl<K: Debug + PartialEq, V: Debug, A: Allocator, H: Hash<K>, E: Equals<K>> Deb
     }
 }
 
+impl<
+        K: PartialEq + Clone,
+        V: Clone,
+        A: Allocator + Clone,
+        H: Hash<K>,
+        E: Equals<K>,
+        C: HashCode,
+    > Clone for HashMap<K, V, A, H, E, C>
+{
+    fn clone(&self) -> Self {
+        Self {
+            hash_table: self.hash_table.clone(),
+        }
+    }
+
+    fn clone_from(&mut self, source: &Self) {
+        self.hash_table.clone_from(&source.hash_table)
+    }
+}
+
 impl<K: PartialEq, V, A: Allocator + Default> Default
     for HashMap<K, V, A, DefaultHash<K>, EqualTo<K>>
 where
@@ -168,6 +434,43 @@ where
     }
 }
 
+impl<K: PartialEq, V, A: Allocator, H: Hash<K>, E: Equals<K>, C: HashCode> Extend<(K, V)>
+    for HashMap<K, V, A, H, E, C>
+{
+    fn extend<T: IntoIterator<Item = (K, V)>>(&mut self, iter: T) {
+        let iter = iter.into_iter();
+        self.hash_table.reserve_for_iter(iter.size_hint().0);
+        iter.for_each(|(k, v)| {
+            self.insert(k, v);
+        });
+    }
+}
+
+impl<
+        'a,
+        K: PartialEq + Copy + 'a,
+        V: Copy + 'a,
+        A: Allocator,
+        H: Hash<K>,
+        E: Equals<K>,
+        C: HashCode,
+    > Extend<(&'a K, &'a V)> for HashMap<K, V, A, H, E, C>
+{
+    fn extend<T: IntoIterator<Item = (&'a K, &'a V)>>(&mut self, iter: T) {
+        self.extend(iter.into_iter().map(|(k, v)| (*k, *v)));
+    }
+}
+
+impl<K: PartialEq, V, const N: usize, A: Allocator + Default> From<[(K, V); N]>
+    for HashMap<K, V, A, DefaultHash<K>, EqualTo<K>>
+where
+    DefaultHash<K>: Hash<K>,
+{
+    fn from(value: [(K, V); N]) -> Self {
+        Self::from_iter(value)
+    }
+}
+
 impl<K: PartialEq, V, A: Allocator + Default> FromIterator<(K, V)>
     for HashMap<K, V, A, DefaultHash<K>, EqualTo<K>>
 where
@@ -180,20 +483,136 @@ where
     }
 }
 
-unsafe impl<K: PartialEq + Send, V: Send, A: Allocator + Send, H: Hash<K>, E: Equals<K>> Send
-    for HashMap<K, V, A, H, E>
+impl<'a, K: PartialEq, V, A: Allocator, H: Hash<K>, E: Equals<K>, C: HashCode> IntoIterator
+    for &'a HashMap<K, V, A, H, E, C>
 {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V, C>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
 }
-unsafe impl<K: PartialEq + Sync, V: Sync, A: Allocator + Sync, H: Hash<K>, E: Equals<K>> Sync
-    for HashMap<K, V, A, H, E>
+
+impl<'a, K: PartialEq, V, A: Allocator, H: Hash<K>, E: Equals<K>, C: HashCode> IntoIterator
+    for &'a mut HashMap<K, V, A, H, E, C>
+{
+    type Item = (&'a K, &'a mut V);
+    type IntoIter = IterMut<'a, K, V, C>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+unsafe impl<K: PartialEq + Send, V: Send, A: Allocator + Send, H: Hash<K>, E: Equals<K>, C: HashCode>
+    Send for HashMap<K, V, A, H, E, C>
+{
+}
+unsafe impl<K: PartialEq + Sync, V: Sync, A: Allocator + Sync, H: Hash<K>, E: Equals<K>, C: HashCode>
+    Sync for HashMap<K, V, A, H, E, C>
 {
 }
 
 #[cfg(test)]
 mod test {
-    use crate::hash_map::DefaultHashMap;
+    use crate::allocator::{CountingAllocator, DefaultAllocator};
+    use crate::equals::EqualTo;
+    use crate::hash::{BorrowHash, CachedHashCode, DefaultHash, Hash};
+    use crate::hash_map::{DefaultHashMap, HashMap};
+    use crate::string::DefaultString;
+    use std::borrow::Borrow;
+    use std::cell::Cell;
     use std::collections::BTreeMap;
 
+    thread_local! {
+        static KEYS_CREATED: Cell<usize> = const { Cell::new(0) };
+        static KEYS_DROPPED: Cell<usize> = const { Cell::new(0) };
+    }
+
+    /// A key which counts how many times it was created from a borrowed key,
+    /// and how many times it was dropped
+    #[derive(PartialEq)]
+    struct CountedKey(u32);
+
+    impl Borrow<u32> for CountedKey {
+        fn borrow(&self) -> &u32 {
+            &self.0
+        }
+    }
+
+    impl From<&u32> for CountedKey {
+        fn from(key: &u32) -> Self {
+            KEYS_CREATED.with(|keys_created| keys_created.set(keys_created.get() + 1));
+            Self(*key)
+        }
+    }
+
+    impl Drop for CountedKey {
+        fn drop(&mut self) {
+            KEYS_DROPPED.with(|keys_dropped| keys_dropped.set(keys_dropped.get() + 1));
+        }
+    }
+
+    impl Hash<CountedKey> for DefaultHash<CountedKey> {
+        fn hash(val: &CountedKey) -> usize {
+            DefaultHash::<u32>::hash(&val.0)
+        }
+    }
+
+    impl BorrowHash<u32> for DefaultHash<CountedKey> {
+        fn hash_borrowed(val: &u32) -> usize {
+            DefaultHash::<u32>::hash(val)
+        }
+    }
+
+    #[test]
+    fn entry_or_default() {
+        let mut hm: DefaultHashMap<u32, u32> = DefaultHashMap::new();
+        assert_eq!(*hm.entry(1).or_default(), 0);
+
+        *hm.entry(1).or_default() += 3;
+        *hm.entry(2).or_default() += 1;
+        assert_eq!(hm.get(&1), Some(&3));
+        assert_eq!(hm.get(&2), Some(&1));
+    }
+
+    #[test]
+    fn entry_or_insert_with_key() {
+        let mut hm: DefaultHashMap<DefaultString, usize> = DefaultHashMap::new();
+        let key = DefaultString::from("hello");
+        assert_eq!(*hm.entry(key).or_insert_with_key(|key| key.len()), 5);
+
+        // the value is only computed for a vacant entry
+        let key = DefaultString::from("hello");
+        assert_eq!(*hm.entry(key).or_insert_with_key(|_| unreachable!()), 5);
+        assert_eq!(hm.len(), 1);
+    }
+
+    #[test]
+    fn entry_ref_occupied() {
+        let mut hm: DefaultHashMap<CountedKey, u32> = DefaultHashMap::new();
+        hm.insert(CountedKey(1), 2);
+        KEYS_CREATED.with(|keys_created| keys_created.set(0));
+
+        *hm.entry_ref(&1).or_insert(5) += 1;
+
+        assert_eq!(KEYS_CREATED.with(Cell::get), 0);
+        assert_eq!(hm.get(&CountedKey(1)), Some(&3));
+    }
+
+    #[test]
+    fn entry_ref_vacant() {
+        let mut hm: DefaultHashMap<CountedKey, u32> = DefaultHashMap::new();
+        KEYS_CREATED.with(|keys_created| keys_created.set(0));
+
+        assert_eq!(*hm.entry_ref(&1).or_insert(5), 5);
+        assert_eq!(*hm.entry_ref(&1).or_insert(6), 5);
+
+        assert_eq!(KEYS_CREATED.with(Cell::get), 1);
+        assert_eq!(hm.len(), 1);
+    }
+
     #[test]
     fn iter() {
         let reference_map: BTreeMap<u32, u32> =
@@ -207,6 +626,166 @@ mod test {
         );
     }
 
+    #[test]
+    fn get_many_mut() {
+        let mut hm: DefaultHashMap<u32, u32> = (0..10).map(|n| (n, n * 10)).collect();
+        let [a, b] = hm.get_many_mut([&2, &7]).unwrap();
+        std::mem::swap(a, b);
+        assert_eq!(hm.get(&2), Some(&70));
+        assert_eq!(hm.get(&7), Some(&20));
+    }
+
+    #[test]
+    fn get_many_mut_duplicate() {
+        let mut hm: DefaultHashMap<u32, u32> = (0..10).map(|n| (n, n * 10)).collect();
+        assert!(hm.get_many_mut([&2, &3, &2]).is_none());
+    }
+
+    #[test]
+    fn get_many_mut_missing() {
+        let mut hm: DefaultHashMap<u32, u32> = (0..10).map(|n| (n, n * 10)).collect();
+        assert!(hm.get_many_mut([&2, &11]).is_none());
+    }
+
+    #[test]
+    fn get_borrowed() {
+        let mut hm: DefaultHashMap<DefaultString, u32> = DefaultHashMap::new();
+        hm.insert("abc".into(), 1);
+        hm.insert("def".into(), 2);
+
+        assert!(hm.contains_key("abc"));
+        assert_eq!(hm.get("def"), Some(&2));
+        assert_eq!(hm.get("ghi"), None);
+        assert_eq!(hm.remove("abc"), Some(1));
+        assert!(!hm.contains_key("abc"));
+    }
+
+    #[test]
+    fn bucket_count() {
+        let mut hm: DefaultHashMap<u32, u32> = DefaultHashMap::new();
+        assert_eq!(hm.bucket_count(), 1);
+        assert_eq!(hm.capacity(), 0);
+
+        for n in 0..10 {
+            hm.insert(n, n);
+        }
+        assert!(hm.bucket_count() > 1);
+        assert!(hm.capacity() >= hm.len());
+        assert_eq!(hm.load_factor(), hm.len() as f32 / hm.bucket_count() as f32);
+    }
+
+    #[test]
+    fn debug_ordered() {
+        let mut forwards: DefaultHashMap<u32, u32> = DefaultHashMap::new();
+        (0..50).for_each(|n| {
+            forwards.insert(n * 7, n);
+        });
+        let mut backwards: DefaultHashMap<u32, u32> = DefaultHashMap::with_bucket_count(200);
+        (0..50).rev().for_each(|n| {
+            backwards.insert(n * 7, n);
+        });
+
+        assert_eq!(
+            format!("{:?}", forwards.debug_ordered()),
+            format!("{:?}", backwards.debug_ordered())
+        );
+
+        let hm: DefaultHashMap<u32, u32> = [(3, 30), (1, 10), (2, 20)].into();
+        assert_eq!(format!("{:?}", hm.debug_ordered()), "{1: 10,2: 20,3: 30}");
+    }
+
+    #[test]
+    fn with_bucket_count() {
+        let mut hm: DefaultHashMap<u32, u32> = DefaultHashMap::with_bucket_count(40);
+        assert_eq!(hm.bucket_count(), 41);
+
+        for n in 0..41 {
+            hm.insert(n, n);
+        }
+        assert_eq!(hm.bucket_count(), 41);
+        assert!((0..41).all(|index| hm.bucket_iter(index).count() == 1));
+    }
+
+    #[test]
+    fn clone() {
+        let hm: DefaultHashMap<u32, DefaultString> = (0..20)
+            .map(|n| (n, DefaultString::from(n.to_string().as_str())))
+            .collect();
+        let cloned = hm.clone();
+        assert_eq!(cloned.len(), 20);
+        assert!(hm.iter().all(|(k, v)| cloned.get(k) == Some(v)));
+    }
+
+    #[test]
+    fn clone_from() {
+        let mut source: HashMap<u32, u32, CountingAllocator> =
+            unsafe { HashMap::new_in(CountingAllocator::default()) };
+        (0..20u32).for_each(|n| {
+            source.insert(n, n * 2);
+        });
+
+        let allocator = CountingAllocator::default();
+        let mut hm: HashMap<u32, u32, CountingAllocator> =
+            unsafe { HashMap::new_in(allocator.clone()) };
+        (100..140u32).for_each(|n| {
+            hm.insert(n, n);
+        });
+        let bucket_count = hm.bucket_count();
+        let allocations = allocator.allocations();
+
+        // only the nodes are allocated, since the buckets are large enough
+        hm.clone_from(&source);
+        assert_eq!(allocator.allocations(), allocations + 20);
+        assert_eq!(hm.bucket_count(), bucket_count);
+        assert_eq!(hm.len(), 20);
+        assert!(source.iter().all(|(k, v)| hm.get(k) == Some(v)));
+        assert!(!hm.contains_key(&100));
+    }
+
+    #[test]
+    fn for_each_mut() {
+        let mut hm: DefaultHashMap<u32, u32> = (0..50).map(|n| (n, 0)).collect();
+        hm.for_each_mut(|k, v| *v = k * k + 1);
+        assert_eq!(hm.len(), 50);
+        assert!((0..50).all(|n| hm.get(&n) == Some(&(n * n + 1))));
+
+        DefaultHashMap::<u32, u32>::new().for_each_mut(|_, _| unreachable!());
+    }
+
+    #[test]
+    fn get_or_insert_default() {
+        let mut hm: DefaultHashMap<u8, u32> = DefaultHashMap::new();
+        for c in "abracadabra".bytes() {
+            *hm.get_or_insert_default(c) += 1;
+        }
+        assert_eq!(hm.len(), 5);
+        assert_eq!(hm.get(&b'a'), Some(&5));
+        assert_eq!(hm.get(&b'b'), Some(&2));
+        assert_eq!(hm.get(&b'r'), Some(&2));
+        assert_eq!(hm.get(&b'c'), Some(&1));
+        assert_eq!(hm.get(&b'd'), Some(&1));
+    }
+
+    #[test]
+    fn into_iter_ref() {
+        let hm: DefaultHashMap<u32, u32> = (0..10).map(|n| (n, n * 2)).collect();
+        let mut sum = 0;
+        for (k, v) in &hm {
+            assert_eq!(*v, *k * 2);
+            sum += *v;
+        }
+        assert_eq!(sum, 90);
+    }
+
+    #[test]
+    fn into_iter_mut() {
+        let mut hm: DefaultHashMap<u32, u32> = (0..10).map(|n| (n, n)).collect();
+        for (_, v) in &mut hm {
+            *v *= 3;
+        }
+        assert!((0..10).all(|n| hm.get(&n) == Some(&(n * 3))));
+    }
+
     #[test]
     fn iter_mut() {
         let mut reference_map: BTreeMap<u32, u32> =
@@ -222,4 +801,146 @@ mod test {
             reference_map
         );
     }
+
+    #[test]
+    fn remove_value_drops_key_once() {
+        let mut hm: DefaultHashMap<CountedKey, u32> = DefaultHashMap::new();
+        hm.insert(CountedKey(1), 2);
+        hm.insert(CountedKey(3), 4);
+        let dropped = KEYS_DROPPED.with(Cell::get);
+
+        assert_eq!(hm.remove_value(&1), Some(2));
+        assert_eq!(KEYS_DROPPED.with(Cell::get), dropped + 1);
+        assert_eq!(hm.remove_value(&1), None);
+        assert_eq!(KEYS_DROPPED.with(Cell::get), dropped + 1);
+
+        assert_eq!(hm.remove(&3), Some(4));
+        assert_eq!(KEYS_DROPPED.with(Cell::get), dropped + 2);
+        assert!(hm.is_empty());
+    }
+
+    #[test]
+    fn try_insert() {
+        let mut hm: DefaultHashMap<u32, u32> = DefaultHashMap::new();
+
+        let inserted = hm.try_insert(1, 2).unwrap();
+        *inserted += 1;
+        assert_eq!(hm.get(&1), Some(&3));
+
+        let err = hm.try_insert(1, 4).unwrap_err();
+        assert_eq!(err.key, &1);
+        assert_eq!(err.existing, &3);
+        assert_eq!(err.value, 4);
+        assert_eq!(hm.get(&1), Some(&3));
+        assert_eq!(hm.len(), 1);
+    }
+
+    #[test]
+    fn extend() {
+        let mut hm: DefaultHashMap<u32, u32> = DefaultHashMap::new();
+        hm.insert(0, 100);
+        hm.extend((0..50).map(|n| (n, n * 2)));
+
+        assert_eq!(hm.len(), 50);
+        // the last writer wins
+        assert_eq!(hm.get(&0), Some(&0));
+        assert_eq!(hm.get(&49), Some(&98));
+
+        hm.extend([(&50, &1), (&51, &2)]);
+        assert_eq!(hm.len(), 52);
+        assert_eq!(hm.get(&51), Some(&2));
+    }
+
+    #[test]
+    fn from_array() {
+        let hm: DefaultHashMap<u32, u32> = DefaultHashMap::from([(1, 2), (3, 4), (1, 5)]);
+
+        assert_eq!(hm.len(), 2);
+        assert_eq!(hm.get(&1), Some(&5));
+        assert_eq!(hm.get(&3), Some(&4));
+    }
+
+    #[test]
+    fn cached_hash_code() {
+        let mut hm: HashMap<
+            u32,
+            u32,
+            DefaultAllocator,
+            DefaultHash<u32>,
+            EqualTo<u32>,
+            CachedHashCode,
+        > = unsafe { HashMap::new_in(DefaultAllocator::default()) };
+        for n in 0..100 {
+            hm.insert(n, n * 2);
+        }
+        assert_eq!(hm.len(), 100);
+        assert!((0..100).all(|n| hm.get(&n) == Some(&(n * 2))));
+        assert_eq!(hm.remove(&50), Some(100));
+        assert_eq!(hm.iter().count(), 99);
+    }
+
+    #[test]
+    fn raw_entry() {
+        let mut hm: DefaultHashMap<DefaultString, u32> = DefaultHashMap::new();
+        let hash = DefaultHash::<DefaultString>::hash(&DefaultString::from("abc"));
+
+        let (key, value) = hm
+            .raw_entry_mut()
+            .from_hash(hash, |k| k.as_str() == "abc")
+            .or_insert_with(|| (DefaultString::from("abc"), 1));
+        assert_eq!(key.as_str(), "abc");
+        *value += 1;
+        assert_eq!(hm.get("abc"), Some(&2));
+
+        hm.raw_entry_mut()
+            .from_hash(hash, |k| k.as_str() == "abc")
+            .and_modify(|_, v| *v *= 10)
+            .or_insert(DefaultString::from("abc"), 0);
+        assert_eq!(hm.len(), 1);
+        assert_eq!(
+            hm.raw_entry().from_hash(hash, |k| k.as_str() == "abc"),
+            Some((&DefaultString::from("abc"), &20))
+        );
+        assert_eq!(
+            hm.raw_entry().from_hash(hash, |k| k.as_str() == "abd"),
+            None
+        );
+    }
+
+    #[test]
+    fn raw_entry_occupied() {
+        let mut hm: DefaultHashMap<u32, u32> = (0..10).map(|n| (n, n * 10)).collect();
+        let hash = DefaultHash::<u32>::hash(&3);
+
+        let mut entry = hm
+            .raw_entry_mut()
+            .from_hash(hash, |k| *k == 3)
+            .occupied()
+            .ok()
+            .expect("3 should be occupied");
+        assert_eq!(*entry.key(), 3);
+        assert_eq!(*entry.get(), 30);
+        *entry.get_mut() += 1;
+        assert_eq!(entry.insert(50), 31);
+        let (key, value) = entry.into_key_value();
+        assert_eq!((*key, *value), (3, 50));
+
+        let entry = hm
+            .raw_entry_mut()
+            .from_hash(hash, |k| *k == 3)
+            .occupied()
+            .ok()
+            .expect("3 should be occupied");
+        assert_eq!(entry.remove_entry(), (3, 50));
+        assert_eq!(hm.len(), 9);
+        assert!(hm.get(&3).is_none());
+        assert!(hm
+            .raw_entry_mut()
+            .from_hash(hash, |k| *k == 3)
+            .occupied()
+            .is_err());
+        assert!((0..10)
+            .filter(|&n| n != 3)
+            .all(|n| hm.get(&n) == Some(&(n * 10))));
+    }
 }
@@ -1,3 +1,6 @@
+use crate::allocator::Allocator;
+use crate::equals::Equals;
+use crate::hash::Hash;
 use crate::internal::hash_table::iter::CompatIter;
 
 /// An iterator that produces keys in a hash set
@@ -58,3 +61,45 @@ impl<'a, K: PartialEq + 'a> Iterator for Iter<'a, K> {
         self.inner.next().map(|(k, _)| k)
     }
 }
+
+/// An iterator which removes and yields every key of a hash set.
+/// Any keys not yielded are removed when the iterator is dropped
+pub struct Drain<'a, K: PartialEq, A: Allocator, H: Hash<K>, E: Equals<K>> {
+    inner: crate::internal::hash_table::iter::Drain<'a, K, (), A, H, E>,
+}
+
+impl<'a, K: PartialEq, A: Allocator, H: Hash<K>, E: Equals<K>> Drain<'a, K, A, H, E> {
+    /// Stops draining and converts the keys which weren't yielded yet into a
+    /// pair of `(begin, end)` compatibility iterators. The remaining keys are
+    /// left in the set, so C++ code can visit and erase them itself
+    pub fn into_compat(self) -> (CompatIter<'a, K, ()>, CompatIter<'a, K, ()>) {
+        self.inner.into_remaining().into_compat()
+    }
+
+    /// Creates a new draining iterator from the hash table's
+    /// draining iterator
+    ///
+    /// # Arguments
+    ///
+    /// `inner`: The hash table's draining iterator
+    pub(crate) fn new(inner: crate::internal::hash_table::iter::Drain<'a, K, (), A, H, E>) -> Self {
+        Self { inner }
+    }
+}
+
+impl<'a, K: PartialEq, A: Allocator, H: Hash<K>, E: Equals<K>> Iterator for Drain<'a, K, A, H, E> {
+    type Item = K;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|(k, _)| k)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<'a, K: PartialEq, A: Allocator, H: Hash<K>, E: Equals<K>> ExactSizeIterator
+    for Drain<'a, K, A, H, E>
+{
+}
@@ -2,12 +2,13 @@ use crate::allocator::DefaultAllocator;
 use crate::equals::{EqualTo, Equals};
 use crate::{
     allocator::Allocator,
-    hash::{DefaultHash, Hash},
+    hash::{BorrowHash, DefaultHash, Hash},
     internal::hash_table::HashTable,
 };
+use std::borrow::Borrow;
 use std::fmt::{Debug, Formatter};
 
-use self::iter::Iter;
+use self::iter::{Drain, Iter};
 
 pub mod iter;
 
@@ -43,12 +44,22 @@ impl<K: PartialEq, A: Allocator, H: Hash<K>, E: Equals<K>> HashSet<K, A, H, E> {
         self.hash_table.clear()
     }
 
+    /// Removes every key from the hash set, returning them in an iterator
+    pub fn drain(&mut self) -> Drain<'_, K, A, H, E> {
+        Drain::new(self.hash_table.drain())
+    }
+
     /// Checks if the hashset contains the given key
     ///
     /// # Arguments
     ///
     /// `key`: The key to search for
-    pub fn contains_key(&self, key: &K) -> bool {
+    pub fn contains_key<Q: ?Sized>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        H: BorrowHash<Q>,
+        E: Equals<Q>,
+    {
         self.hash_table.contains_key(key)
     }
 
@@ -57,19 +68,23 @@ impl<K: PartialEq, A: Allocator, H: Hash<K>, E: Equals<K>> HashSet<K, A, H, E> {
     /// # Arguments
     ///
     /// `key`: The key to search for
-    pub fn get(&self, key: &K) -> Option<&K> {
+    pub fn get<Q: ?Sized>(&self, key: &Q) -> Option<&K>
+    where
+        K: Borrow<Q>,
+        H: BorrowHash<Q>,
+        E: Equals<Q>,
+    {
         self.hash_table.get(key).map(|(k, _)| k)
     }
 
-    /// Inserts the key pair into the hashset. Returns true on success
+    /// Inserts the key into the hashset. Returns true if the key was not
+    /// already present
     ///
     /// # Arguments
     ///
-    /// `key`: The key with which to insert the pair
-    ///
-    /// `value`: The associated value
+    /// `key`: The key to insert
     pub fn insert(&mut self, key: K) -> bool {
-        self.hash_table.insert(key, ()).is_some()
+        self.hash_table.insert(key, ()).is_none()
     }
 
     /// Creates a hash set backed by an allocator
@@ -87,13 +102,46 @@ impl<K: PartialEq, A: Allocator, H: Hash<K>, E: Equals<K>> HashSet<K, A, H, E> {
         }
     }
 
+    /// Returns true if the hash set has no keys in common with `other`
+    ///
+    /// # Arguments
+    ///
+    /// `other`: The set to compare against
+    pub fn is_disjoint(&self, other: &Self) -> bool {
+        // probe the larger set with the keys of the smaller one
+        let (smaller, larger) = if self.len() <= other.len() {
+            (self, other)
+        } else {
+            (other, self)
+        };
+        smaller.iter().all(|key| !larger.contains_key(key))
+    }
+
     /// Returns true if the hash table is empty
     pub fn is_empty(&self) -> bool {
         self.hash_table.is_empty()
     }
 
+    /// Returns true if every key in the hash set is also in `other`
+    ///
+    /// # Arguments
+    ///
+    /// `other`: The set to compare against
+    pub fn is_subset(&self, other: &Self) -> bool {
+        self.len() <= other.len() && self.iter().all(|key| other.contains_key(key))
+    }
+
+    /// Returns true if every key in `other` is also in the hash set
+    ///
+    /// # Arguments
+    ///
+    /// `other`: The set to compare against
+    pub fn is_superset(&self, other: &Self) -> bool {
+        other.is_subset(self)
+    }
+
     /// Returns an iterator over the hash set's keys
-    pub fn iter(&self) -> Iter<K> {
+    pub fn iter(&self) -> Iter<'_, K> {
         Iter::new(self.hash_table.iter())
     }
 
@@ -103,7 +151,12 @@ impl<K: PartialEq, A: Allocator, H: Hash<K>, E: Equals<K>> HashSet<K, A, H, E> {
     }
 
     /// Removes a key from the hash set, returning it if it was found
-    pub fn remove(&mut self, key: &K) -> Option<K> {
+    pub fn remove<Q: ?Sized>(&mut self, key: &Q) -> Option<K>
+    where
+        K: Borrow<Q>,
+        H: BorrowHash<Q>,
+        E: Equals<Q>,
+    {
         self.hash_table.remove_entry(key).map(|(key, _)| key)
     }
 }
@@ -130,6 +183,34 @@ where
     }
 }
 
+impl<K: PartialEq, A: Allocator, H: Hash<K>, E: Equals<K>> Extend<K> for HashSet<K, A, H, E> {
+    fn extend<T: IntoIterator<Item = K>>(&mut self, iter: T) {
+        let iter = iter.into_iter();
+        self.hash_table.reserve_for_iter(iter.size_hint().0);
+        iter.for_each(|k| {
+            self.insert(k);
+        });
+    }
+}
+
+impl<'a, K: PartialEq + Copy + 'a, A: Allocator, H: Hash<K>, E: Equals<K>> Extend<&'a K>
+    for HashSet<K, A, H, E>
+{
+    fn extend<T: IntoIterator<Item = &'a K>>(&mut self, iter: T) {
+        self.extend(iter.into_iter().copied());
+    }
+}
+
+impl<K: PartialEq, const N: usize, A: Allocator + Default> From<[K; N]>
+    for HashSet<K, A, DefaultHash<K>, EqualTo<K>>
+where
+    DefaultHash<K>: Hash<K>,
+{
+    fn from(value: [K; N]) -> Self {
+        Self::from_iter(value)
+    }
+}
+
 impl<K: PartialEq, A: Allocator + Default> FromIterator<K>
     for HashSet<K, A, DefaultHash<K>, EqualTo<K>>
 where
@@ -142,6 +223,17 @@ where
     }
 }
 
+impl<'a, K: PartialEq, A: Allocator, H: Hash<K>, E: Equals<K>> IntoIterator
+    for &'a HashSet<K, A, H, E>
+{
+    type Item = &'a K;
+    type IntoIter = Iter<'a, K>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
 unsafe impl<K: PartialEq + Send, A: Allocator + Send, H: Hash<K>, E: Equals<K>> Send
     for HashSet<K, A, H, E>
 {
@@ -153,7 +245,9 @@ unsafe impl<K: PartialEq + Sync, A: Allocator + Sync, H: Hash<K>, E: Equals<K>>
 
 #[cfg(test)]
 mod test {
+    use crate::hash_set::iter::Iter;
     use crate::hash_set::DefaultHashSet;
+    use crate::internal::hash_table::iter::CompatIter;
     use std::collections::BTreeSet;
 
     #[test]
@@ -162,4 +256,121 @@ mod test {
         let hm: DefaultHashSet<u32> = reference_map.iter().copied().collect();
         assert_eq!(hm.iter().copied().collect::<BTreeSet<u32>>(), reference_map);
     }
+
+    #[test]
+    fn into_iter_ref() {
+        let hs: DefaultHashSet<u32> = (0..10).collect();
+        let mut seen = BTreeSet::new();
+        for k in &hs {
+            seen.insert(*k);
+        }
+        assert_eq!(seen, (0..10).collect::<BTreeSet<u32>>());
+    }
+
+    #[test]
+    fn set_relations() {
+        let small: DefaultHashSet<u32> = DefaultHashSet::from([1, 2]);
+        let large: DefaultHashSet<u32> = DefaultHashSet::from([1, 2, 3]);
+        let overlapping: DefaultHashSet<u32> = DefaultHashSet::from([2, 4]);
+        let disjoint: DefaultHashSet<u32> = DefaultHashSet::from([5, 6, 7, 8]);
+
+        assert!(small.is_subset(&large));
+        assert!(!large.is_subset(&small));
+        assert!(large.is_superset(&small));
+        assert!(!small.is_superset(&large));
+        assert!(small.is_subset(&small) && small.is_superset(&small));
+
+        assert!(!small.is_subset(&overlapping));
+        assert!(!small.is_superset(&overlapping));
+        assert!(!small.is_disjoint(&overlapping));
+
+        assert!(large.is_disjoint(&disjoint));
+        assert!(disjoint.is_disjoint(&large));
+        assert!(DefaultHashSet::new().is_subset(&small));
+    }
+
+    #[test]
+    fn insert() {
+        let mut hs: DefaultHashSet<u32> = DefaultHashSet::new();
+        assert!(hs.insert(1));
+        assert!(!hs.insert(1));
+        assert_eq!(hs.len(), 1);
+    }
+
+    #[test]
+    fn extend() {
+        let mut hs: DefaultHashSet<u32> = DefaultHashSet::from([0, 1]);
+        hs.extend(0..10);
+        assert_eq!(hs.len(), 10);
+        assert!((0..10).all(|n| hs.contains_key(&n)));
+
+        hs.extend(&[10, 11, 10]);
+        assert_eq!(hs.len(), 12);
+        assert!(hs.contains_key(&11));
+    }
+
+    #[test]
+    fn from_array() {
+        let hs: DefaultHashSet<u32> = DefaultHashSet::from([1, 2, 3, 2]);
+        assert_eq!(hs.len(), 3);
+        assert!(hs.contains_key(&2));
+        assert!(!hs.contains_key(&4));
+    }
+
+    #[test]
+    fn compat_layout() {
+        // EASTL's hashtable iterator holds a node pointer followed by a bucket pointer
+        assert_eq!(
+            std::mem::size_of::<CompatIter<u32, ()>>(),
+            std::mem::size_of::<usize>() * 2
+        );
+
+        let hs: DefaultHashSet<u32> = [5].into();
+        let (begin, _) = hs.iter().into_compat();
+        let [node_ptr, bucket_ptr] =
+            unsafe { std::mem::transmute::<CompatIter<u32, ()>, [*const u32; 2]>(begin) };
+        // the key is the first field of the node
+        assert_eq!(unsafe { *node_ptr }, 5);
+        assert!(!bucket_ptr.is_null());
+    }
+
+    #[test]
+    fn compat_round_trip() {
+        let hs: DefaultHashSet<u32> = (0..100).collect();
+        let (begin, end) = hs.iter().into_compat();
+        let iter = unsafe { Iter::from_compat(begin, end) };
+        assert_eq!(iter.copied().collect::<BTreeSet<u32>>(), (0..100).collect());
+    }
+
+    #[test]
+    fn drain() {
+        let mut hs: DefaultHashSet<u32> = (0..100).collect();
+        let drained: BTreeSet<u32> = hs.drain().collect();
+        assert_eq!(drained, (0..100).collect());
+        assert!(hs.is_empty());
+
+        // dropping a drain removes the rest
+        hs.extend(0..10);
+        assert_eq!(hs.drain().take(3).count(), 3);
+        assert!(hs.is_empty());
+        assert_eq!(hs.iter().count(), 0);
+    }
+
+    #[test]
+    fn drain_into_compat() {
+        let mut hs: DefaultHashSet<u32> = (0..100).collect();
+        let mut drain = hs.drain();
+        let mut seen: BTreeSet<u32> = drain.by_ref().take(40).collect();
+
+        // the rest are left in the set and visited through the compat iterators
+        let (begin, end) = drain.into_compat();
+        let remaining: BTreeSet<u32> = unsafe { Iter::from_compat(begin, end) }.copied().collect();
+        assert_eq!(remaining.len(), 60);
+        assert!(seen.is_disjoint(&remaining));
+        seen.extend(remaining);
+        assert_eq!(seen, (0..100).collect());
+
+        assert_eq!(hs.len(), 60);
+        assert!(hs.iter().all(|k| seen.contains(k)));
+    }
 }
@@ -1,25 +1,60 @@
 use crate::allocator::Allocator;
 use crate::equals::Equals;
-use crate::hash::Hash;
+use crate::hash::{Hash, HashCode, NoHashCode};
 use crate::internal::hash_table::node::Node;
 use crate::internal::hash_table::HashTable;
 
 /// A vacant node - one that has not been inserted yet.
-pub struct VacantEntry<'a, K: PartialEq, V, A: Allocator, H: Hash<K>, E: Equals<K>> {
-    pub(crate) table: &'a mut HashTable<K, V, A, H, E>,
-    pub(crate) target_bucket: &'a mut *mut Node<K, V>,
+pub struct VacantEntry<
+    'a,
+    K: PartialEq,
+    V,
+    A: Allocator,
+    H: Hash<K>,
+    E: Equals<K>,
+    C: HashCode = NoHashCode,
+> {
+    pub(crate) table: &'a mut HashTable<K, V, A, H, E, C>,
+    pub(crate) target_bucket: &'a mut *mut Node<K, V, C>,
+    pub(crate) hash: usize,
     pub(crate) key: K,
 }
 
+impl<'a, K: PartialEq, V, A: Allocator, H: Hash<K>, E: Equals<K>, C: HashCode>
+    VacantEntry<'a, K, V, A, H, E, C>
+{
+    /// Inserts the value into the table with the entry's key.
+    ///
+    /// # Arguments
+    ///
+    /// `value`: The value to insert.
+    pub fn insert(self, value: V) -> &'a mut V {
+        &mut self
+            .table
+            .insert_impl(self.target_bucket, self.hash, self.key, value)
+            .val
+    }
+}
+
 /// An entry in a hash table.
-pub enum Entry<'a, K: PartialEq, V, A: Allocator, H: Hash<K>, E: Equals<K>> {
+pub enum Entry<
+    'a,
+    K: PartialEq,
+    V,
+    A: Allocator,
+    H: Hash<K>,
+    E: Equals<K>,
+    C: HashCode = NoHashCode,
+> {
     /// There was a node found already for the key.
-    Occupied(&'a mut Node<K, V>),
+    Occupied(&'a mut Node<K, V, C>),
     /// There was not a node already present for the key.
-    Vacant(VacantEntry<'a, K, V, A, H, E>),
+    Vacant(VacantEntry<'a, K, V, A, H, E, C>),
 }
 
-impl<'a, K: PartialEq, V, A: Allocator, H: Hash<K>, E: Equals<K>> Entry<'a, K, V, A, H, E> {
+impl<'a, K: PartialEq, V, A: Allocator, H: Hash<K>, E: Equals<K>, C: HashCode>
+    Entry<'a, K, V, A, H, E, C>
+{
     /// Provides in-place mutable access to the value.
     ///
     /// # Arguments
@@ -48,23 +83,386 @@ impl<'a, K: PartialEq, V, A: Allocator, H: Hash<K>, E: Equals<K>> Entry<'a, K, V
     ///
     /// `default`: A function producing a default value.
     pub fn or_insert_with<F: FnOnce() -> V>(self, default: F) -> &'a mut V {
+        match self {
+            Self::Occupied(v) => &mut v.val,
+            Self::Vacant(entry) => entry.insert(default()),
+        }
+    }
+
+    /// Fetches the value stored in the entry, or inserts a default key
+    /// produced from the entry's key.
+    ///
+    /// # Arguments
+    ///
+    /// `default`: A function producing a default value from the key.
+    pub fn or_insert_with_key<F: FnOnce(&K) -> V>(self, default: F) -> &'a mut V {
         match self {
             Self::Occupied(v) => &mut v.val,
             Self::Vacant(entry) => {
+                let value = default(&entry.key);
+                entry.insert(value)
+            }
+        }
+    }
+}
+
+/// A vacant node looked up by a borrowed key - the owned key is only
+/// created once a value is inserted.
+pub struct VacantEntryRef<
+    'a,
+    'b,
+    K: PartialEq,
+    Q: ?Sized,
+    V,
+    A: Allocator,
+    H: Hash<K>,
+    E: Equals<K>,
+    C: HashCode = NoHashCode,
+> {
+    pub(crate) table: &'a mut HashTable<K, V, A, H, E, C>,
+    pub(crate) target_bucket: &'a mut *mut Node<K, V, C>,
+    pub(crate) hash: usize,
+    pub(crate) key: &'b Q,
+}
+
+/// An entry in a hash table, looked up by a borrowed key.
+pub enum EntryRef<
+    'a,
+    'b,
+    K: PartialEq,
+    Q: ?Sized,
+    V,
+    A: Allocator,
+    H: Hash<K>,
+    E: Equals<K>,
+    C: HashCode = NoHashCode,
+> {
+    /// There was a node found already for the key.
+    Occupied(&'a mut Node<K, V, C>),
+    /// There was not a node already present for the key.
+    Vacant(VacantEntryRef<'a, 'b, K, Q, V, A, H, E, C>),
+}
+
+impl<'a, 'b, K: PartialEq, Q: ?Sized, V, A: Allocator, H: Hash<K>, E: Equals<K>, C: HashCode>
+    EntryRef<'a, 'b, K, Q, V, A, H, E, C>
+{
+    /// Provides in-place mutable access to the value.
+    ///
+    /// # Arguments
+    ///
+    /// `f`: A function taking a mutable reference to the value.
+    pub fn and_modify<F: Fn(&mut V)>(mut self, f: F) -> Self {
+        if let Self::Occupied(occupied) = &mut self {
+            f(&mut occupied.val);
+        }
+
+        self
+    }
+
+    /// Fetches the value stored in the entry, or inserts a default key.
+    ///
+    /// # Arguments
+    ///
+    /// `default`: The default value.
+    pub fn or_insert(self, default: V) -> &'a mut V
+    where
+        K: From<&'b Q>,
+    {
+        self.or_insert_with(|| default)
+    }
+
+    /// Fetches the value stored in the entry, or inserts a default key.
+    ///
+    /// # Arguments
+    ///
+    /// `default`: A function producing a default value.
+    pub fn or_insert_with<F: FnOnce() -> V>(self, default: F) -> &'a mut V
+    where
+        K: From<&'b Q>,
+    {
+        self.or_insert_with_owned_key(K::from, default)
+    }
+
+    /// Fetches the value stored in the entry, or inserts a default key,
+    /// creating the owned key with a function.
+    ///
+    /// # Arguments
+    ///
+    /// `to_owned`: A function producing the owned key from the borrowed key.
+    ///
+    /// `default`: A function producing a default value.
+    pub fn or_insert_with_owned_key<F: FnOnce(&'b Q) -> K, G: FnOnce() -> V>(
+        self,
+        to_owned: F,
+        default: G,
+    ) -> &'a mut V {
+        match self {
+            Self::Occupied(v) => &mut v.val,
+            Self::Vacant(entry) => {
+                let key = to_owned(entry.key);
                 let val = default();
                 &mut entry
                     .table
-                    .insert_impl(entry.target_bucket, entry.key, val)
+                    .insert_impl(entry.target_bucket, entry.hash, key, val)
                     .val
             }
         }
     }
 }
 
+/// Looks up pairs in a hash table by a precomputed hash and a key-matching
+/// function, rather than the table's hash and equality functors.
+pub struct RawEntryBuilder<
+    'a,
+    K: PartialEq,
+    V,
+    A: Allocator,
+    H: Hash<K>,
+    E: Equals<K>,
+    C: HashCode = NoHashCode,
+> {
+    pub(crate) table: &'a HashTable<K, V, A, H, E, C>,
+}
+
+impl<'a, K: PartialEq, V, A: Allocator, H: Hash<K>, E: Equals<K>, C: HashCode>
+    RawEntryBuilder<'a, K, V, A, H, E, C>
+{
+    /// Fetches the pair whose key matches, if there is one.
+    ///
+    /// # Arguments
+    ///
+    /// `hash`: The hash of the key, which must equal the table's hash of it.
+    ///
+    /// `is_match`: A function returning true for the matching key.
+    #[allow(clippy::wrong_self_convention)]
+    pub fn from_hash<F: FnMut(&K) -> bool>(
+        self,
+        hash: usize,
+        is_match: F,
+    ) -> Option<(&'a K, &'a V)> {
+        let bucket = unsafe { (*self.table.bucket_for_hash(hash)).as_ref() };
+        HashTable::<K, V, A, H, E, C>::find_in_bucket_by(bucket, is_match)
+            .map(|node| (node.key(), node.value()))
+    }
+}
+
+/// Looks up entries in a hash table by a precomputed hash and a key-matching
+/// function, rather than the table's hash and equality functors.
+pub struct RawEntryBuilderMut<
+    'a,
+    K: PartialEq,
+    V,
+    A: Allocator,
+    H: Hash<K>,
+    E: Equals<K>,
+    C: HashCode = NoHashCode,
+> {
+    pub(crate) table: &'a mut HashTable<K, V, A, H, E, C>,
+}
+
+impl<'a, K: PartialEq, V, A: Allocator, H: Hash<K>, E: Equals<K>, C: HashCode>
+    RawEntryBuilderMut<'a, K, V, A, H, E, C>
+{
+    /// Fetches the entry whose key matches.
+    ///
+    /// # Arguments
+    ///
+    /// `hash`: The hash of the key, which must equal the table's hash of it.
+    ///
+    /// `is_match`: A function returning true for the matching key.
+    #[allow(clippy::wrong_self_convention)]
+    pub fn from_hash<F: FnMut(&K) -> bool>(
+        self,
+        hash: usize,
+        mut is_match: F,
+    ) -> RawEntryMut<'a, K, V, A, H, E, C> {
+        // trail behind by one pointer so an occupied entry can unlink itself
+        let target_bucket = self.table.bucket_for_hash_mut(hash);
+        let mut link = target_bucket;
+        unsafe {
+            while !(*link).is_null() && !is_match(&(**link).key) {
+                link = &mut (**link).next;
+            }
+            if (*link).is_null() {
+                RawEntryMut::Vacant(RawVacantEntryMut {
+                    table: self.table,
+                    target_bucket: &mut *target_bucket,
+                    hash,
+                })
+            } else {
+                RawEntryMut::Occupied(RawOccupiedEntryMut {
+                    table: self.table,
+                    link: &mut *link,
+                })
+            }
+        }
+    }
+}
+
+/// An occupied raw entry - a pair matching the lookup is in the table.
+pub struct RawOccupiedEntryMut<
+    'a,
+    K: PartialEq,
+    V,
+    A: Allocator,
+    H: Hash<K>,
+    E: Equals<K>,
+    C: HashCode = NoHashCode,
+> {
+    pub(crate) table: &'a mut HashTable<K, V, A, H, E, C>,
+    /// The pointer to the node, either in the bucket array or in the previous node
+    pub(crate) link: &'a mut *mut Node<K, V, C>,
+}
+
+impl<'a, K: PartialEq, V, A: Allocator, H: Hash<K>, E: Equals<K>, C: HashCode>
+    RawOccupiedEntryMut<'a, K, V, A, H, E, C>
+{
+    /// Returns the key of the entry.
+    pub fn key(&self) -> &K {
+        unsafe { &(**self.link).key }
+    }
+
+    /// Returns the value of the entry.
+    pub fn get(&self) -> &V {
+        unsafe { &(**self.link).val }
+    }
+
+    /// Returns the value of the entry mutably.
+    pub fn get_mut(&mut self) -> &mut V {
+        unsafe { &mut (**self.link).val }
+    }
+
+    /// Converts the entry into references to its key and value.
+    pub fn into_key_value(self) -> (&'a K, &'a mut V) {
+        let node = unsafe { &mut **self.link };
+        (&node.key, &mut node.val)
+    }
+
+    /// Replaces the value of the entry, returning the old value.
+    ///
+    /// # Arguments
+    ///
+    /// `value`: The new value.
+    pub fn insert(&mut self, value: V) -> V {
+        std::mem::replace(self.get_mut(), value)
+    }
+
+    /// Removes the pair from the table, returning it.
+    pub fn remove_entry(self) -> (K, V) {
+        unsafe {
+            let node = *self.link;
+            *self.link = (*node).next;
+            self.table.element_count -= 1;
+            let key = std::ptr::read(&(*node).key);
+            let value = std::ptr::read(&(*node).val);
+            // the key and value were moved out, so free without dropping
+            self.table.allocator.deallocate(node, 1);
+            (key, value)
+        }
+    }
+}
+
+/// A vacant raw entry - the key is only provided once a pair is inserted.
+pub struct RawVacantEntryMut<
+    'a,
+    K: PartialEq,
+    V,
+    A: Allocator,
+    H: Hash<K>,
+    E: Equals<K>,
+    C: HashCode = NoHashCode,
+> {
+    pub(crate) table: &'a mut HashTable<K, V, A, H, E, C>,
+    pub(crate) target_bucket: &'a mut *mut Node<K, V, C>,
+    pub(crate) hash: usize,
+}
+
+impl<'a, K: PartialEq, V, A: Allocator, H: Hash<K>, E: Equals<K>, C: HashCode>
+    RawVacantEntryMut<'a, K, V, A, H, E, C>
+{
+    /// Inserts the pair into the table. The key must hash to the entry's hash.
+    ///
+    /// # Arguments
+    ///
+    /// `key`: The key to insert.
+    ///
+    /// `value`: The value to insert.
+    pub fn insert(self, key: K, value: V) -> (&'a K, &'a mut V) {
+        debug_assert_eq!(H::hash(&key), self.hash, "the key must match the raw hash");
+        let node = self
+            .table
+            .insert_impl(self.target_bucket, self.hash, key, value);
+        (&node.key, &mut node.val)
+    }
+}
+
+/// An entry in a hash table, looked up by a precomputed hash.
+pub enum RawEntryMut<
+    'a,
+    K: PartialEq,
+    V,
+    A: Allocator,
+    H: Hash<K>,
+    E: Equals<K>,
+    C: HashCode = NoHashCode,
+> {
+    /// There was a node found already for the key.
+    Occupied(RawOccupiedEntryMut<'a, K, V, A, H, E, C>),
+    /// There was not a node already present for the key.
+    Vacant(RawVacantEntryMut<'a, K, V, A, H, E, C>),
+}
+
+impl<'a, K: PartialEq, V, A: Allocator, H: Hash<K>, E: Equals<K>, C: HashCode>
+    RawEntryMut<'a, K, V, A, H, E, C>
+{
+    /// Provides in-place mutable access to the value.
+    ///
+    /// # Arguments
+    ///
+    /// `f`: A function taking the key and a mutable reference to the value.
+    pub fn and_modify<F: FnOnce(&K, &mut V)>(mut self, f: F) -> Self {
+        if let Self::Occupied(occupied) = &mut self {
+            let node = unsafe { &mut **occupied.link };
+            f(&node.key, &mut node.val);
+        }
+
+        self
+    }
+
+    /// Fetches the pair stored in the entry, or inserts a default pair.
+    ///
+    /// # Arguments
+    ///
+    /// `key`: The default key, which must hash to the entry's hash.
+    ///
+    /// `value`: The default value.
+    pub fn or_insert(self, key: K, value: V) -> (&'a K, &'a mut V) {
+        self.or_insert_with(|| (key, value))
+    }
+
+    /// Fetches the pair stored in the entry, or inserts a default pair.
+    ///
+    /// # Arguments
+    ///
+    /// `default`: A function producing the default pair, whose key must hash
+    /// to the entry's hash.
+    pub fn or_insert_with<F: FnOnce() -> (K, V)>(self, default: F) -> (&'a K, &'a mut V) {
+        match self {
+            Self::Occupied(entry) => entry.into_key_value(),
+            Self::Vacant(entry) => {
+                let (key, value) = default();
+                entry.insert(key, value)
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
-    use crate::internal::hash_table::entry::Entry;
-    use crate::internal::hash_table::DefaultHashTable;
+    use crate::allocator::DefaultAllocator;
+    use crate::hash::CountingHash;
+    use crate::internal::hash_table::entry::{Entry, EntryRef};
+    use crate::internal::hash_table::{DefaultHashTable, HashTable};
 
     #[test]
     fn occupied() {
@@ -122,4 +520,42 @@ mod test {
 
         assert!(matches!(ht.entry("abc"), Entry::Vacant(_)));
     }
+
+    #[test]
+    fn entry_ref_occupied() {
+        let mut ht = DefaultHashTable::new();
+        ht.insert(1u32, 5);
+
+        assert!(matches!(ht.entry_ref(&1), EntryRef::Occupied(_)));
+    }
+
+    #[test]
+    fn entry_ref_or_insert_with_owned_key() {
+        let mut ht = DefaultHashTable::new();
+        ht.insert(1u32, 5);
+        let mut keys_created = 0;
+
+        for key in [1, 2, 2] {
+            ht.entry_ref(&key).or_insert_with_owned_key(
+                |key| {
+                    keys_created += 1;
+                    *key
+                },
+                || 6,
+            );
+        }
+        assert_eq!(keys_created, 1);
+        assert_eq!(ht.get(&2), Some((&2, &6)));
+    }
+
+    #[test]
+    fn vacant_hashes_once() {
+        let mut ht: DefaultHashTable<u32, u32, CountingHash> =
+            unsafe { HashTable::new_in(DefaultAllocator::default()) };
+        CountingHash::reset();
+
+        assert_eq!(*ht.entry(1).or_insert_with(|| 5), 5);
+        assert_eq!(CountingHash::hashes(), 1);
+        assert_eq!(ht.get(&1), Some((&1, &5)));
+    }
 }
@@ -1,14 +1,19 @@
 use super::node::Node;
+use super::HashTable;
+use crate::allocator::Allocator;
+use crate::equals::Equals;
+use crate::hash::{Hash, HashCode, NoHashCode};
 use std::marker::PhantomData;
+use std::mem::ManuallyDrop;
 
 /// A compatibility iterator for C++ iterators.
 /// Tho concept of begin and end are not used in
 /// rust, so these are strictly for compatibility's
 /// sake. They cannot actually be used to iterate.
 #[repr(C)]
-pub struct CompatIter<'a, K: PartialEq + 'a, V: 'a> {
-    node_ptr: *mut Node<K, V>,
-    bucket_ptr: *const *const Node<K, V>,
+pub struct CompatIter<'a, K: PartialEq + 'a, V: 'a, C: 'a = NoHashCode> {
+    node_ptr: *mut Node<K, V, C>,
+    bucket_ptr: *const *const Node<K, V, C>,
     _marker: PhantomData<&'a (K, V)>,
 }
 
@@ -17,9 +22,9 @@ pub struct CompatIter<'a, K: PartialEq + 'a, V: 'a> {
 /// rust, so these are strictly for compatibility's
 /// sake. They cannot actually be used to iterate.
 #[repr(C)]
-pub struct CompatIterMut<'a, K: PartialEq + 'a, V: 'a> {
-    node_ptr: *mut Node<K, V>,
-    bucket_ptr: *const *mut Node<K, V>,
+pub struct CompatIterMut<'a, K: PartialEq + 'a, V: 'a, C: 'a = NoHashCode> {
+    node_ptr: *mut Node<K, V, C>,
+    bucket_ptr: *const *mut Node<K, V, C>,
     _marker: PhantomData<&'a (K, V)>,
 }
 
@@ -36,25 +41,25 @@ pub struct CompatIterMut<'a, K: PartialEq + 'a, V: 'a> {
 /// inserted after an iterator was created will
 /// be yielded by the iterator
 #[derive(Clone)]
-struct RawIter<'a, K: PartialEq + 'a, V: 'a> {
-    bucket_iter: std::slice::Iter<'a, *mut Node<K, V>>,
-    node_ptr: *mut Node<K, V>,
+struct RawIter<'a, K: PartialEq + 'a, V: 'a, C: 'a = NoHashCode> {
+    bucket_iter: std::slice::Iter<'a, *mut Node<K, V, C>>,
+    node_ptr: *mut Node<K, V, C>,
 }
 
-impl<'a, K: PartialEq, V> RawIter<'a, K, V> {
+impl<'a, K: PartialEq, V, C> RawIter<'a, K, V, C> {
     /// Converts the Rust iterator into a pair of
     /// `(begin, end)` compatibility iterators
-    fn into_compat(self) -> (CompatIter<'a, K, V>, CompatIter<'a, K, V>) {
+    fn into_compat(self) -> (CompatIter<'a, K, V, C>, CompatIter<'a, K, V, C>) {
         (
-            CompatIter::<'a, K, V> {
+            CompatIter::<'a, K, V, C> {
                 node_ptr: self.node_ptr,
-                bucket_ptr: self.bucket_iter.as_slice().as_ptr() as *const *const Node<K, V>,
+                bucket_ptr: self.bucket_iter.as_slice().as_ptr() as *const *const Node<K, V, C>,
                 _marker: PhantomData,
             },
-            CompatIter::<'a, K, V> {
+            CompatIter::<'a, K, V, C> {
                 node_ptr: std::ptr::null_mut(),
                 bucket_ptr: unsafe {
-                    (self.bucket_iter.as_slice().as_ptr() as *const *const Node<K, V>)
+                    (self.bucket_iter.as_slice().as_ptr() as *const *const Node<K, V, C>)
                         .add(self.bucket_iter.as_slice().len())
                 },
                 _marker: PhantomData,
@@ -69,14 +74,14 @@ impl<'a, K: PartialEq, V> RawIter<'a, K, V> {
     ///
     /// Mutability of the exposed iterator must be enforced.
     /// POLLO: Is there a better way to do this?
-    unsafe fn into_compat_mut(self) -> (CompatIterMut<'a, K, V>, CompatIterMut<'a, K, V>) {
+    unsafe fn into_compat_mut(self) -> (CompatIterMut<'a, K, V, C>, CompatIterMut<'a, K, V, C>) {
         (
-            CompatIterMut::<'a, K, V> {
+            CompatIterMut::<'a, K, V, C> {
                 node_ptr: self.node_ptr,
                 bucket_ptr: self.bucket_iter.as_slice().as_ptr(),
                 _marker: PhantomData,
             },
-            CompatIterMut::<'a, K, V> {
+            CompatIterMut::<'a, K, V, C> {
                 node_ptr: std::ptr::null_mut(),
                 bucket_ptr: self
                     .bucket_iter
@@ -101,10 +106,10 @@ impl<'a, K: PartialEq, V> RawIter<'a, K, V> {
     /// `begin`: The starting compatibility iterator
     ///
     /// `end`: The ending compatibility iterator
-    unsafe fn from_compat(begin: CompatIter<K, V>, end: CompatIter<K, V>) -> Self {
+    unsafe fn from_compat(begin: CompatIter<K, V, C>, end: CompatIter<K, V, C>) -> Self {
         Self {
             bucket_iter: std::slice::from_raw_parts(
-                begin.bucket_ptr as *const *mut Node<K, V>,
+                begin.bucket_ptr as *const *mut Node<K, V, C>,
                 end.bucket_ptr.offset_from(begin.bucket_ptr) as usize,
             )
             .iter(),
@@ -125,7 +130,7 @@ impl<'a, K: PartialEq, V> RawIter<'a, K, V> {
     /// `begin`: The starting compatibility iterator
     ///
     /// `end`: The ending compatibility iterator
-    unsafe fn from_compat_mut(begin: CompatIterMut<K, V>, end: CompatIterMut<K, V>) -> Self {
+    unsafe fn from_compat_mut(begin: CompatIterMut<K, V, C>, end: CompatIterMut<K, V, C>) -> Self {
         Self {
             node_ptr: begin.node_ptr,
             bucket_iter: std::slice::from_raw_parts(
@@ -143,7 +148,7 @@ impl<'a, K: PartialEq, V> RawIter<'a, K, V> {
     ///
     /// `buckets`: The slice of buckets owned by the
     /// hash table
-    fn new(buckets: &'a [*mut Node<K, V>]) -> Self {
+    fn new(buckets: &'a [*mut Node<K, V, C>]) -> Self {
         let mut new_iter = Self {
             node_ptr: std::ptr::null_mut(),
             bucket_iter: buckets.iter(),
@@ -154,7 +159,7 @@ impl<'a, K: PartialEq, V> RawIter<'a, K, V> {
     }
 
     /// Finds the next non-null bucket
-    fn next_bucket(&mut self) -> Option<*mut Node<K, V>> {
+    fn next_bucket(&mut self) -> Option<*mut Node<K, V, C>> {
         self.bucket_iter
             .by_ref()
             .find(|&&ptr| !ptr.is_null())
@@ -162,7 +167,7 @@ impl<'a, K: PartialEq, V> RawIter<'a, K, V> {
     }
 }
 
-impl<'a, K: PartialEq, V> Iterator for RawIter<'a, K, V> {
+impl<'a, K: PartialEq, V, C> Iterator for RawIter<'a, K, V, C> {
     type Item = (&'a K, &'a mut V);
 
     fn next(&mut self) -> Option<Self::Item> {
@@ -196,14 +201,14 @@ impl<'a, K: PartialEq, V> Iterator for RawIter<'a, K, V> {
 /// inserted after an iterator was created will
 /// be yielded by the iterator
 #[derive(Clone)]
-pub struct Iter<'a, K: PartialEq + 'a, V: 'a> {
-    inner: RawIter<'a, K, V>,
+pub struct Iter<'a, K: PartialEq + 'a, V: 'a, C: 'a = NoHashCode> {
+    inner: RawIter<'a, K, V, C>,
 }
 
-impl<'a, K: PartialEq + 'a, V: 'a> Iter<'a, K, V> {
+impl<'a, K: PartialEq + 'a, V: 'a, C: 'a> Iter<'a, K, V, C> {
     /// Converts the Rust iterator into a pair of
     /// `(begin, end)` compatibility iterators
-    pub fn into_compat(self) -> (CompatIter<'a, K, V>, CompatIter<'a, K, V>) {
+    pub fn into_compat(self) -> (CompatIter<'a, K, V, C>, CompatIter<'a, K, V, C>) {
         self.inner.into_compat()
     }
 
@@ -220,7 +225,10 @@ impl<'a, K: PartialEq + 'a, V: 'a> Iter<'a, K, V> {
     /// `begin`: The starting compatibility iterator
     ///
     /// `end`: The ending compatibility iterator
-    pub unsafe fn from_compat(begin: CompatIter<'a, K, V>, end: CompatIter<'a, K, V>) -> Self {
+    pub unsafe fn from_compat(
+        begin: CompatIter<'a, K, V, C>,
+        end: CompatIter<'a, K, V, C>,
+    ) -> Self {
         Self {
             inner: RawIter::from_compat(begin, end),
         }
@@ -239,7 +247,10 @@ impl<'a, K: PartialEq + 'a, V: 'a> Iter<'a, K, V> {
     /// `begin`: The starting compatibility iterator
     ///
     /// `end`: The ending compatibility iterator
-    pub unsafe fn from_compat_mut(begin: CompatIterMut<K, V>, end: CompatIterMut<K, V>) -> Self {
+    pub unsafe fn from_compat_mut(
+        begin: CompatIterMut<K, V, C>,
+        end: CompatIterMut<K, V, C>,
+    ) -> Self {
         Self {
             inner: RawIter::from_compat_mut(begin, end),
         }
@@ -252,14 +263,14 @@ impl<'a, K: PartialEq + 'a, V: 'a> Iter<'a, K, V> {
     ///
     /// `buckets`: The slice of buckets owned by the
     /// hash table
-    pub(crate) fn new(buckets: &'a [*mut Node<K, V>]) -> Self {
+    pub(crate) fn new(buckets: &'a [*mut Node<K, V, C>]) -> Self {
         Self {
             inner: RawIter::new(buckets),
         }
     }
 }
 
-impl<'a, K: PartialEq + 'a, V: 'a> Iterator for Iter<'a, K, V> {
+impl<'a, K: PartialEq + 'a, V: 'a, C: 'a> Iterator for Iter<'a, K, V, C> {
     type Item = (&'a K, &'a V);
 
     fn next(&mut self) -> Option<Self::Item> {
@@ -281,20 +292,20 @@ impl<'a, K: PartialEq + 'a, V: 'a> Iterator for Iter<'a, K, V> {
 /// inserted after an iterator was created will
 /// be yielded by the iterator
 #[derive(Clone)]
-pub struct IterMut<'a, K: PartialEq + 'a, V: 'a> {
-    inner: RawIter<'a, K, V>,
+pub struct IterMut<'a, K: PartialEq + 'a, V: 'a, C: 'a = NoHashCode> {
+    inner: RawIter<'a, K, V, C>,
 }
 
-impl<'a, K: PartialEq + 'a, V: 'a> IterMut<'a, K, V> {
+impl<'a, K: PartialEq + 'a, V: 'a, C: 'a> IterMut<'a, K, V, C> {
     /// Converts the Rust iterator into a pair of
     /// `(begin, end)` compatibility iterators
-    pub fn into_compat(self) -> (CompatIter<'a, K, V>, CompatIter<'a, K, V>) {
+    pub fn into_compat(self) -> (CompatIter<'a, K, V, C>, CompatIter<'a, K, V, C>) {
         self.inner.into_compat()
     }
 
     /// Converts the Rust iterator into a pair of
     /// mutable `(begin, end)` compatibility iterators
-    pub fn into_compat_mut(self) -> (CompatIterMut<'a, K, V>, CompatIterMut<'a, K, V>) {
+    pub fn into_compat_mut(self) -> (CompatIterMut<'a, K, V, C>, CompatIterMut<'a, K, V, C>) {
         unsafe { self.inner.into_compat_mut() }
     }
 
@@ -311,7 +322,7 @@ impl<'a, K: PartialEq + 'a, V: 'a> IterMut<'a, K, V> {
     /// `begin`: The starting compatibility iterator
     ///
     /// `end`: The ending compatibility iterator
-    pub unsafe fn from_compat(begin: CompatIter<K, V>, end: CompatIter<K, V>) -> Self {
+    pub unsafe fn from_compat(begin: CompatIter<K, V, C>, end: CompatIter<K, V, C>) -> Self {
         Self {
             inner: RawIter::from_compat(begin, end),
         }
@@ -324,14 +335,14 @@ impl<'a, K: PartialEq + 'a, V: 'a> IterMut<'a, K, V> {
     ///
     /// `buckets`: The slice of buckets owned by the
     /// hash table
-    pub(crate) fn new(buckets: &'a [*mut Node<K, V>]) -> Self {
+    pub(crate) fn new(buckets: &'a [*mut Node<K, V, C>]) -> Self {
         Self {
             inner: RawIter::new(buckets),
         }
     }
 }
 
-impl<'a, K: PartialEq + 'a, V: 'a> Iterator for IterMut<'a, K, V> {
+impl<'a, K: PartialEq + 'a, V: 'a, C: 'a> Iterator for IterMut<'a, K, V, C> {
     type Item = (&'a K, &'a mut V);
 
     fn next(&mut self) -> Option<Self::Item> {
@@ -339,6 +350,89 @@ impl<'a, K: PartialEq + 'a, V: 'a> Iterator for IterMut<'a, K, V> {
     }
 }
 
+/// An iterator which removes and yields every key-value pair of a hash
+/// table, in the same order as `Iter`. Any pairs not yielded are removed
+/// when the iterator is dropped
+pub struct Drain<
+    'a,
+    K: PartialEq,
+    V,
+    A: Allocator,
+    H: Hash<K>,
+    E: Equals<K>,
+    C: HashCode = NoHashCode,
+> {
+    table: &'a mut HashTable<K, V, A, H, E, C>,
+    bucket: usize,
+}
+
+impl<'a, K: PartialEq, V, A: Allocator, H: Hash<K>, E: Equals<K>, C: HashCode>
+    Drain<'a, K, V, A, H, E, C>
+{
+    /// Creates a draining iterator over a hash table
+    ///
+    /// # Arguments
+    ///
+    /// `table`: The hash table to drain
+    pub(crate) fn new(table: &'a mut HashTable<K, V, A, H, E, C>) -> Self {
+        Self { table, bucket: 0 }
+    }
+
+    /// Stops draining, leaving the pairs which weren't yielded yet in the table.
+    /// Returns an iterator over the remaining pairs
+    pub fn into_remaining(self) -> Iter<'a, K, V, C> {
+        let this = ManuallyDrop::new(self);
+        // safety: the drain is never used again, so the table is only borrowed here
+        let table: &'a HashTable<K, V, A, H, E, C> = unsafe { &*(this.table as *const _) };
+        Iter::new(&table.buckets_imut()[this.bucket.min(table.bucket_count())..])
+    }
+}
+
+impl<'a, K: PartialEq, V, A: Allocator, H: Hash<K>, E: Equals<K>, C: HashCode> Iterator
+    for Drain<'a, K, V, A, H, E, C>
+{
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.bucket < self.table.bucket_count() {
+            let bucket = unsafe { &mut *self.table.bucket_array.add(self.bucket) };
+            if bucket.is_null() {
+                self.bucket += 1;
+                continue;
+            }
+
+            // unlink the head of the bucket and move its pair out
+            let node = *bucket;
+            unsafe {
+                *bucket = (*node).next;
+                self.table.element_count -= 1;
+                let key = std::ptr::read(&(*node).key);
+                let value = std::ptr::read(&(*node).val);
+                self.table.allocator.deallocate(node, 1);
+                return Some((key, value));
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.table.len(), Some(self.table.len()))
+    }
+}
+
+impl<'a, K: PartialEq, V, A: Allocator, H: Hash<K>, E: Equals<K>, C: HashCode> ExactSizeIterator
+    for Drain<'a, K, V, A, H, E, C>
+{
+}
+
+impl<'a, K: PartialEq, V, A: Allocator, H: Hash<K>, E: Equals<K>, C: HashCode> Drop
+    for Drain<'a, K, V, A, H, E, C>
+{
+    fn drop(&mut self) {
+        self.for_each(drop);
+    }
+}
+
 #[cfg(test)]
 mod test {
     use crate::internal::hash_table::DefaultHashTable;
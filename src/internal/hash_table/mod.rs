@@ -1,16 +1,19 @@
+use std::borrow::Borrow;
 use std::marker::PhantomData;
 
 #[cfg(test)]
 use crate::allocator::DefaultAllocator;
 use crate::equals::{EqualTo, Equals};
-use crate::internal::hash_table::entry::{Entry, VacantEntry};
+use crate::internal::hash_table::entry::{
+    Entry, EntryRef, RawEntryBuilder, RawEntryBuilderMut, VacantEntry, VacantEntryRef,
+};
 use crate::{
     allocator::Allocator,
-    hash::{DefaultHash, Hash},
+    hash::{BorrowHash, DefaultHash, Hash, HashCode, NoHashCode},
 };
 
 use self::{
-    iter::{Iter, IterMut},
+    iter::{Drain, Iter, IterMut},
     node::Node,
     rehash_policy::PrimeRehashPolicy,
 };
@@ -25,7 +28,11 @@ mod rehash_policy;
 pub type DefaultHashTable<K, V, H = DefaultHash<K>, E = EqualTo<K>> =
     HashTable<K, V, DefaultAllocator, H, E>;
 
-/// A base hashtable used to support hash maps and sets
+/// A base hashtable used to support hash maps and sets.
+///
+/// Each pair lives in its own node, so node addresses are stable for as
+/// long as the pair is in the table - a re-hash only relinks the nodes into
+/// the new buckets. Pointers held by C++ remain valid across any insertion
 #[repr(C)]
 pub struct HashTable<
     K: PartialEq,
@@ -33,16 +40,17 @@ pub struct HashTable<
     A: Allocator,
     H: Hash<K> = DefaultHash<K>,
     E: Equals<K> = EqualTo<K>,
+    C: HashCode = NoHashCode,
 > {
     /// The C++ object has some key extractor functor here
     /// that we don't need
     _pad: u8,
-    bucket_array: *mut *mut Node<K, V>,
+    bucket_array: *mut *mut Node<K, V, C>,
     bucket_count: u32,
     element_count: u32,
     rehash_policy: PrimeRehashPolicy,
     allocator: A,
-    _markers: PhantomData<(K, V, H, E)>,
+    _markers: PhantomData<(K, V, H, E, C)>,
 }
 
 /// Two entries - a null entry and the sentinel.
@@ -56,9 +64,71 @@ where
     pub fn new() -> Self {
         unsafe { Self::new_in(A::default()) }
     }
+
+    /// Creates an empty hashtable with at least the given number of buckets,
+    /// rounded up to the next prime
+    ///
+    /// # Arguments
+    ///
+    /// `bucket_count`: The minimum number of buckets
+    pub fn with_bucket_count(bucket_count: usize) -> Self {
+        let mut ht = Self::new();
+        if bucket_count > 0 {
+            let bucket_count = ht
+                .rehash_policy
+                .get_bucket_count(bucket_count.min(u32::MAX as usize) as u32);
+            ht.rehash(bucket_count);
+        }
+        ht
+    }
 }
 
-impl<K: PartialEq, V, A: Allocator, H: Hash<K>, E: Equals<K>> HashTable<K, V, A, H, E> {
+impl<K: PartialEq, V, A: Allocator, H: Hash<K>, E: Equals<K>, C: HashCode>
+    HashTable<K, V, A, H, E, C>
+{
+    /// Returns the number of buckets in the hash table
+    pub fn bucket_count(&self) -> usize {
+        self.bucket_count as usize
+    }
+
+    /// Returns an iterator over the key-value pairs in a single bucket
+    ///
+    /// # Arguments
+    ///
+    /// `index`: The index of the bucket, which must be less than `bucket_count()`
+    pub fn bucket_iter(&self, index: usize) -> impl Iterator<Item = (&K, &V)> {
+        assert!(
+            index < self.bucket_count(),
+            "bucket index ({index}) out of bounds for {} buckets",
+            self.bucket_count()
+        );
+        std::iter::successors(unsafe { self.buckets()[index].as_ref() }, |node| {
+            node.next()
+        })
+        .map(|node| (node.key(), node.value()))
+    }
+
+    /// Returns the number of entries in the bucket which a key maps to
+    ///
+    /// # Arguments
+    ///
+    /// `key`: The key used to pick the bucket
+    pub fn bucket_len(&self, key: &K) -> usize {
+        self.bucket_iter(Self::bucket_index(self.bucket_count, key))
+            .count()
+    }
+
+    /// Returns the number of elements the hash table can hold before it
+    /// needs to rehash
+    pub fn capacity(&self) -> usize {
+        // the shared empty bucket array can't hold any elements
+        if self.bucket_count == 1 {
+            0
+        } else {
+            (self.bucket_count as f32 * self.rehash_policy.max_load_factor()) as usize
+        }
+    }
+
     /// Clears the hash table, removing all key-value pairs
     pub fn clear(&mut self) {
         self.free_buckets();
@@ -70,15 +140,27 @@ impl<K: PartialEq, V, A: Allocator, H: Hash<K>, E: Equals<K>> HashTable<K, V, A,
     /// # Arguments
     ///
     /// `key`: The key to search for
-    pub fn contains_key(&self, key: &K) -> bool {
+    pub fn contains_key<Q: ?Sized>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        H: BorrowHash<Q>,
+        E: Equals<Q>,
+    {
         self.get(key).is_some()
     }
 
+    /// Removes every key-value pair from the hash table, returning them in
+    /// an iterator. The bucket array is kept
+    pub fn drain(&mut self) -> Drain<'_, K, V, A, H, E, C> {
+        Drain::new(self)
+    }
+
     /// Gets the given key’s corresponding entry in the map for in-place manipulation.
     ///
     /// `key`: The key.
-    pub fn entry(&mut self, key: K) -> Entry<K, V, A, H, E> {
-        let target_bucket = self.bucket_for_key_mut(&key);
+    pub fn entry(&mut self, key: K) -> Entry<'_, K, V, A, H, E, C> {
+        let hash = H::hash(&key);
+        let target_bucket = self.bucket_for_hash_mut(hash);
         if let Some(existing_node) =
             Self::find_in_bucket_mut(unsafe { (*target_bucket).as_mut() }, &key)
         {
@@ -87,17 +169,65 @@ impl<K: PartialEq, V, A: Allocator, H: Hash<K>, E: Equals<K>> HashTable<K, V, A,
             Entry::Vacant(VacantEntry {
                 table: self,
                 target_bucket: unsafe { &mut *target_bucket },
+                hash,
                 key,
             })
         }
     }
 
+    /// Gets the given key’s corresponding entry in the map for in-place manipulation,
+    /// only creating an owned key if the entry is vacant and a value is inserted.
+    ///
+    /// `key`: The borrowed key.
+    pub fn entry_ref<'b, Q: ?Sized>(&mut self, key: &'b Q) -> EntryRef<'_, 'b, K, Q, V, A, H, E, C>
+    where
+        K: Borrow<Q>,
+        H: BorrowHash<Q>,
+        E: Equals<Q>,
+    {
+        let hash = H::hash_borrowed(key);
+        let target_bucket = self.bucket_for_hash_mut(hash);
+        if let Some(existing_node) =
+            Self::find_in_bucket_mut(unsafe { (*target_bucket).as_mut() }, key)
+        {
+            EntryRef::Occupied(existing_node)
+        } else {
+            EntryRef::Vacant(VacantEntryRef {
+                table: self,
+                target_bucket: unsafe { &mut *target_bucket },
+                hash,
+                key,
+            })
+        }
+    }
+
+    /// Calls `f` with every key and mutable value in the hash table, walking
+    /// the buckets directly rather than through an iterator
+    ///
+    /// # Arguments
+    ///
+    /// `f`: The function to call with each pair
+    pub fn for_each_mut<F: FnMut(&K, &mut V)>(&mut self, mut f: F) {
+        for &bucket in self.buckets_imut() {
+            let mut current_node = bucket;
+            while let Some(node) = unsafe { current_node.as_mut() } {
+                f(&node.key, &mut node.val);
+                current_node = node.next;
+            }
+        }
+    }
+
     /// Fetches the associated value for a key
     ///
     /// # Arguments
     ///
     /// `key`: The key to search for
-    pub fn get(&self, key: &K) -> Option<(&K, &V)> {
+    pub fn get<Q: ?Sized>(&self, key: &Q) -> Option<(&K, &V)>
+    where
+        K: Borrow<Q>,
+        H: BorrowHash<Q>,
+        E: Equals<Q>,
+    {
         let bucket = unsafe { (*self.bucket_for_key(key)).as_ref() };
         Self::find_in_bucket(bucket, key).map(|node| (node.key(), node.value()))
     }
@@ -107,11 +237,92 @@ impl<K: PartialEq, V, A: Allocator, H: Hash<K>, E: Equals<K>> HashTable<K, V, A,
     /// # Arguments
     ///
     /// `key`: The key to search for
-    pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+    pub fn get_mut<Q: ?Sized>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        H: BorrowHash<Q>,
+        E: Equals<Q>,
+    {
         let bucket = unsafe { (*self.bucket_for_key_mut(key)).as_mut() };
         Self::find_in_bucket_mut(bucket, key).map(|node| node.value_mut())
     }
 
+    /// Fetches a pointer to the node holding a key, for building C++-compatible
+    /// iterators. The pointer stays valid until the pair is removed
+    ///
+    /// # Arguments
+    ///
+    /// `key`: The key to search for
+    pub fn get_node_ptr<Q: ?Sized>(&self, key: &Q) -> Option<*const Node<K, V, C>>
+    where
+        K: Borrow<Q>,
+        H: BorrowHash<Q>,
+        E: Equals<Q>,
+    {
+        let bucket = unsafe { (*self.bucket_for_key(key)).as_ref() };
+        Self::find_in_bucket(bucket, key).map(|node| node as *const Node<K, V, C>)
+    }
+
+    /// Fetches a mutable pointer to the node holding a key, for building
+    /// C++-compatible iterators. The pointer stays valid until the pair is removed
+    ///
+    /// # Arguments
+    ///
+    /// `key`: The key to search for
+    pub fn get_node_ptr_mut<Q: ?Sized>(&mut self, key: &Q) -> Option<*mut Node<K, V, C>>
+    where
+        K: Borrow<Q>,
+        H: BorrowHash<Q>,
+        E: Equals<Q>,
+    {
+        let bucket = unsafe { (*self.bucket_for_key_mut(key)).as_mut() };
+        Self::find_in_bucket_mut(bucket, key).map(|node| node as *mut Node<K, V, C>)
+    }
+
+    /// Fetches mutable references to the values associated with several
+    /// keys at once. Returns `None` if any of the keys are missing, or if
+    /// any two of the keys are equal
+    ///
+    /// # Arguments
+    ///
+    /// `keys`: The keys to search for
+    pub fn get_many_mut<const N: usize>(&mut self, keys: [&K; N]) -> Option<[&mut V; N]> {
+        // the keys must be disjoint, otherwise we'd hand out aliasing references
+        for (index, key) in keys.iter().enumerate() {
+            if keys[..index].iter().any(|other| E::equals(key, other)) {
+                return None;
+            }
+        }
+        let mut values = [std::ptr::null_mut::<V>(); N];
+        for (value, key) in values.iter_mut().zip(keys) {
+            *value = self.get_mut(key)?;
+        }
+        // safety: every key is distinct and therefore lives in a distinct node
+        Some(values.map(|value| unsafe { &mut *value }))
+    }
+
+    /// Fetches the value indexed by the key, inserting the default value
+    /// first if the key isn't present
+    ///
+    /// # Arguments
+    ///
+    /// `key`: The key to index the pair
+    pub fn get_or_insert_default(&mut self, key: K) -> &mut V
+    where
+        V: Default,
+    {
+        let hash = H::hash(&key);
+        let target_bucket = self.bucket_for_hash_mut(hash);
+        if let Some(existing_node) =
+            Self::find_in_bucket_mut(unsafe { (*target_bucket).as_mut() }, &key)
+        {
+            existing_node.value_mut()
+        } else {
+            self.insert_impl(target_bucket, hash, key, V::default())
+                .value_mut()
+        }
+    }
+
     /// Inserts the key-value pair into the hash table
     ///
     /// # Arguments
@@ -120,18 +331,62 @@ impl<K: PartialEq, V, A: Allocator, H: Hash<K>, E: Equals<K>> HashTable<K, V, A,
     ///
     /// `value`: The associated value
     pub fn insert(&mut self, key: K, value: V) -> Option<V> {
-        let target_bucket = self.bucket_for_key_mut(&key);
+        let hash = H::hash(&key);
+        let target_bucket = self.bucket_for_hash_mut(hash);
         if let Some(existing_node) =
             Self::find_in_bucket_mut(unsafe { (*target_bucket).as_mut() }, &key)
         {
             Some(std::mem::replace(existing_node.value_mut(), value))
         } else {
-            self.insert_impl(target_bucket, key, value);
+            self.insert_impl(target_bucket, hash, key, value);
 
             None
         }
     }
 
+    /// Reserves space so that at least `additional` more elements can be
+    /// inserted without re-hashing
+    ///
+    /// # Arguments
+    ///
+    /// `additional`: The number of additional elements
+    ///
+    /// # Panics
+    ///
+    /// Panics if the total number of elements would overflow a `u32`
+    pub fn reserve(&mut self, additional: usize) {
+        let additional = u32::try_from(additional)
+            .ok()
+            .filter(|additional| self.element_count.checked_add(*additional).is_some())
+            .expect("capacity overflow");
+        if let Some(bucket_count) = self.rehash_policy.get_rehash_required(
+            self.bucket_count,
+            self.element_count,
+            additional,
+        ) {
+            self.rehash(bucket_count);
+        }
+    }
+
+    /// Reserves space for the elements an iterator is expected to yield. Like
+    /// std, a non-empty table only reserves half of the lower bound since many
+    /// keys may already be present. A hint which couldn't fit in the table
+    /// can't be honest, so nothing is reserved for it
+    ///
+    /// # Arguments
+    ///
+    /// `lower_bound`: The lower bound of the iterator's size hint
+    pub(crate) fn reserve_for_iter(&mut self, lower_bound: usize) {
+        let additional = if self.is_empty() {
+            lower_bound
+        } else {
+            lower_bound.div_ceil(2)
+        };
+        if additional <= (u32::MAX - self.element_count) as usize {
+            self.reserve(additional);
+        }
+    }
+
     /// Returns true if the hash table is empty
     pub fn is_empty(&self) -> bool {
         self.len() == 0
@@ -139,14 +394,14 @@ impl<K: PartialEq, V, A: Allocator, H: Hash<K>, E: Equals<K>> HashTable<K, V, A,
 
     /// Returns an iterator over the hash table's
     /// key-value pairs
-    pub fn iter(&self) -> Iter<K, V> {
+    pub fn iter(&self) -> Iter<'_, K, V, C> {
         Iter::new(self.buckets_imut())
     }
 
     /// Returns an iterator over the hash table's
     /// key-value pairs, where the values are
     /// mutable
-    pub fn iter_mut(&mut self) -> IterMut<K, V> {
+    pub fn iter_mut(&mut self) -> IterMut<'_, K, V, C> {
         IterMut::new(self.buckets_imut())
     }
 
@@ -155,6 +410,11 @@ impl<K: PartialEq, V, A: Allocator, H: Hash<K>, E: Equals<K>> HashTable<K, V, A,
         self.element_count as usize
     }
 
+    /// Returns the average number of elements per bucket
+    pub fn load_factor(&self) -> f32 {
+        self.element_count as f32 / self.bucket_count as f32
+    }
+
     /// Creates a hash table backed by an allocator
     ///
     /// # Arguments
@@ -168,7 +428,9 @@ impl<K: PartialEq, V, A: Allocator, H: Hash<K>, E: Equals<K>> HashTable<K, V, A,
         Self {
             _pad: 0,
             bucket_array: unsafe {
-                std::mem::transmute::<*const usize, *mut *mut Node<K, V>>(EMPTY_BUCKET_ARR.as_ptr())
+                std::mem::transmute::<*const usize, *mut *mut Node<K, V, C>>(
+                    EMPTY_BUCKET_ARR.as_ptr(),
+                )
             },
             bucket_count: 1,
             element_count: 0,
@@ -178,14 +440,31 @@ impl<K: PartialEq, V, A: Allocator, H: Hash<K>, E: Equals<K>> HashTable<K, V, A,
         }
     }
 
+    /// Creates a builder to look up pairs by a precomputed hash and a
+    /// key-matching function, without the table's functors
+    pub fn raw_entry(&self) -> RawEntryBuilder<'_, K, V, A, H, E, C> {
+        RawEntryBuilder { table: self }
+    }
+
+    /// Creates a builder to look up or insert entries by a precomputed hash
+    /// and a key-matching function, without the table's functors
+    pub fn raw_entry_mut(&mut self) -> RawEntryBuilderMut<'_, K, V, A, H, E, C> {
+        RawEntryBuilderMut { table: self }
+    }
+
     /// Removes a key-value pair from the hash table,
     /// returning the element if it was found
     ///
     /// # Arguments
     ///
     /// `key`: The key to index the pair
-    pub fn remove(&mut self, key: &K) -> Option<V> {
-        self.remove_entry(key).map(|(_, val)| val)
+    pub fn remove<Q: ?Sized>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        H: BorrowHash<Q>,
+        E: Equals<Q>,
+    {
+        self.remove_value(key)
     }
 
     /// Removes a key-value pair from the hash table,
@@ -194,12 +473,60 @@ impl<K: PartialEq, V, A: Allocator, H: Hash<K>, E: Equals<K>> HashTable<K, V, A,
     /// # Arguments
     ///
     /// `key`: The key to index the pair
-    pub fn remove_entry(&mut self, key: &K) -> Option<(K, V)> {
+    pub fn remove_entry<Q: ?Sized>(&mut self, key: &Q) -> Option<(K, V)>
+    where
+        K: Borrow<Q>,
+        H: BorrowHash<Q>,
+        E: Equals<Q>,
+    {
+        self.unlink(key).map(|node| unsafe {
+            let key = std::ptr::read(&(*node).key);
+            let value = std::ptr::read(&(*node).val);
+            // notice we don't drop the key or value here.
+            // we don't want to drop them now and still have
+            // binary copies of them existing
+            self.allocator.deallocate(node, 1);
+            (key, value)
+        })
+    }
+
+    /// Removes a key-value pair from the hash table, returning the value if
+    /// it was found. The key is dropped in place rather than being moved out
+    ///
+    /// # Arguments
+    ///
+    /// `key`: The key to index the pair
+    pub fn remove_value<Q: ?Sized>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        H: BorrowHash<Q>,
+        E: Equals<Q>,
+    {
+        self.unlink(key).map(|node| unsafe {
+            std::ptr::drop_in_place(&mut (*node).key);
+            let value = std::ptr::read(&(*node).val);
+            self.allocator.deallocate(node, 1);
+            value
+        })
+    }
+
+    /// Unlinks the node for a key from its bucket, returning the node if it
+    /// was found. The node's contents are left intact and it is not freed
+    ///
+    /// # Arguments
+    ///
+    /// `key`: The key to index the node
+    fn unlink<Q: ?Sized>(&mut self, key: &Q) -> Option<*mut Node<K, V, C>>
+    where
+        K: Borrow<Q>,
+        H: BorrowHash<Q>,
+        E: Equals<Q>,
+    {
         // we need to trail behind by one so we can
         // update the correct pointer
         let mut bucket = self.bucket_for_key_mut(key);
         unsafe {
-            while !(*bucket).is_null() && !E::equals((**bucket).key(), key) {
+            while !(*bucket).is_null() && !E::equals((**bucket).key().borrow(), key) {
                 bucket = &mut (**bucket).next;
             }
             if (*bucket).is_null() {
@@ -207,14 +534,8 @@ impl<K: PartialEq, V, A: Allocator, H: Hash<K>, E: Equals<K>> HashTable<K, V, A,
             } else {
                 let node = *bucket;
                 (*bucket) = (**bucket).next;
-                let key = std::ptr::read(&(*node).key);
-                let value = std::ptr::read(&(*node).val);
-                // notice we don't drop the key or value here.
-                // we don't want to drop them now and still have
-                // binary copies of them existing
-                self.allocator.deallocate(node, 1);
                 self.element_count -= 1;
-                Some((key, value))
+                Some(node)
             }
         }
     }
@@ -224,7 +545,10 @@ impl<K: PartialEq, V, A: Allocator, H: Hash<K>, E: Equals<K>> HashTable<K, V, A,
     /// # Arguments
     ///
     /// `key`: The key
-    fn bucket_for_key(&self, key: &K) -> *const *const Node<K, V> {
+    fn bucket_for_key<Q: ?Sized>(&self, key: &Q) -> *const *const Node<K, V, C>
+    where
+        H: BorrowHash<Q>,
+    {
         &self.buckets()[Self::bucket_index(self.bucket_count, key)]
     }
 
@@ -233,13 +557,34 @@ impl<K: PartialEq, V, A: Allocator, H: Hash<K>, E: Equals<K>> HashTable<K, V, A,
     /// # Arguments
     ///
     /// `key`: The key
-    fn bucket_for_key_mut(&mut self, key: &K) -> *mut *mut Node<K, V> {
+    fn bucket_for_key_mut<Q: ?Sized>(&mut self, key: &Q) -> *mut *mut Node<K, V, C>
+    where
+        H: BorrowHash<Q>,
+    {
         unsafe {
             self.bucket_array
                 .add(Self::bucket_index(self.bucket_count, key))
         }
     }
 
+    /// Fetches the bucket for a precomputed key hash
+    ///
+    /// # Arguments
+    ///
+    /// `hash`: The hash of the key
+    fn bucket_for_hash(&self, hash: usize) -> *const *const Node<K, V, C> {
+        &self.buckets()[hash % self.bucket_count as usize]
+    }
+
+    /// Fetches the bucket for a precomputed key hash
+    ///
+    /// # Arguments
+    ///
+    /// `hash`: The hash of the key
+    fn bucket_for_hash_mut(&mut self, hash: usize) -> *mut *mut Node<K, V, C> {
+        unsafe { self.bucket_array.add(hash % self.bucket_count as usize) }
+    }
+
     /// Returns the index of the bucket for the given
     /// hash key
     ///
@@ -248,23 +593,26 @@ impl<K: PartialEq, V, A: Allocator, H: Hash<K>, E: Equals<K>> HashTable<K, V, A,
     /// `bucket_count`: The total number of buckets
     ///
     /// `key`: The key
-    fn bucket_index(bucket_count: u32, key: &K) -> usize {
-        let key_hash = H::hash(key);
+    fn bucket_index<Q: ?Sized>(bucket_count: u32, key: &Q) -> usize
+    where
+        H: BorrowHash<Q>,
+    {
+        let key_hash = H::hash_borrowed(key);
         key_hash % bucket_count as usize
     }
 
     /// Returns the buckets for the hash table
-    fn buckets(&self) -> &[*const Node<K, V>] {
+    fn buckets(&self) -> &[*const Node<K, V, C>] {
         unsafe {
             std::slice::from_raw_parts(
-                self.bucket_array as *const *const Node<K, V>,
+                self.bucket_array as *const *const Node<K, V, C>,
                 self.bucket_count as usize,
             )
         }
     }
 
     /// Returns the buckets for the hash table
-    fn buckets_imut(&self) -> &[*mut Node<K, V>] {
+    fn buckets_imut(&self) -> &[*mut Node<K, V, C>] {
         if let Some(bucket_array) = unsafe { self.bucket_array.as_ref() } {
             unsafe { std::slice::from_raw_parts(bucket_array, self.bucket_count as usize) }
         } else {
@@ -273,7 +621,7 @@ impl<K: PartialEq, V, A: Allocator, H: Hash<K>, E: Equals<K>> HashTable<K, V, A,
     }
 
     /// Returns the buckets for the hash table
-    fn buckets_mut(&mut self) -> &mut [*mut Node<K, V>] {
+    fn buckets_mut(&mut self) -> &mut [*mut Node<K, V, C>] {
         if let Some(bucket_array) = unsafe { self.bucket_array.as_mut() } {
             unsafe { std::slice::from_raw_parts_mut(bucket_array, self.bucket_count as usize) }
         } else {
@@ -286,9 +634,16 @@ impl<K: PartialEq, V, A: Allocator, H: Hash<K>, E: Equals<K>> HashTable<K, V, A,
     /// # Arguments
     ///
     /// `bucket`: The bucket to search in
-    fn find_in_bucket<'a>(mut bucket: Option<&'a Node<K, V>>, key: &K) -> Option<&'a Node<K, V>> {
+    fn find_in_bucket<'a, Q: ?Sized>(
+        mut bucket: Option<&'a Node<K, V, C>>,
+        key: &Q,
+    ) -> Option<&'a Node<K, V, C>>
+    where
+        K: Borrow<Q>,
+        E: Equals<Q>,
+    {
         while let Some(node) = bucket {
-            if E::equals(node.key(), key) {
+            if E::equals(node.key().borrow(), key) {
                 return Some(node);
             }
             bucket = node.next();
@@ -301,12 +656,16 @@ impl<K: PartialEq, V, A: Allocator, H: Hash<K>, E: Equals<K>> HashTable<K, V, A,
     /// # Arguments
     ///
     /// `bucket`:
-    fn find_in_bucket_mut<'a>(
-        mut bucket: Option<&'a mut Node<K, V>>,
-        key: &K,
-    ) -> Option<&'a mut Node<K, V>> {
+    fn find_in_bucket_mut<'a, Q: ?Sized>(
+        mut bucket: Option<&'a mut Node<K, V, C>>,
+        key: &Q,
+    ) -> Option<&'a mut Node<K, V, C>>
+    where
+        K: Borrow<Q>,
+        E: Equals<Q>,
+    {
         while let Some(node) = bucket {
-            if E::equals(node.key(), key) {
+            if E::equals(node.key().borrow(), key) {
                 return Some(node);
             }
             bucket = node.next_mut();
@@ -314,23 +673,59 @@ impl<K: PartialEq, V, A: Allocator, H: Hash<K>, E: Equals<K>> HashTable<K, V, A,
         None
     }
 
+    /// Finds the first node in a bucket whose key matches
+    ///
+    /// # Arguments
+    ///
+    /// `bucket`: The bucket to search in
+    ///
+    /// `is_match`: A function returning true for the matching key
+    fn find_in_bucket_by<F: FnMut(&K) -> bool>(
+        mut bucket: Option<&Node<K, V, C>>,
+        mut is_match: F,
+    ) -> Option<&Node<K, V, C>> {
+        while let Some(node) = bucket {
+            if is_match(node.key()) {
+                return Some(node);
+            }
+            bucket = node.next();
+        }
+        None
+    }
+
     /// Frees a bucket and all of the child nodes
     ///
     /// # Arguments
     ///
     /// `bucket_node`: The node in the bucket
-    fn free_bucket(&mut self, bucket_node: &mut Node<K, V>) {
+    fn free_bucket(&mut self, bucket_node: &mut Node<K, V, C>) {
         // free the next node
         if let Some(next_node) = bucket_node.next_mut() {
             self.free_bucket(next_node);
         }
         // drop and free our node
         unsafe {
-            std::ptr::drop_in_place(bucket_node as *mut Node<K, V>);
+            std::ptr::drop_in_place(bucket_node as *mut Node<K, V, C>);
             self.allocator.deallocate(bucket_node, 1)
         }
     }
 
+    /// Frees the bucket array, leaving the table with the shared empty bucket
+    /// array. The buckets must already be empty
+    fn free_bucket_array(&mut self) {
+        if self.bucket_count > 1 {
+            // the array has an extra slot for the sentinel
+            unsafe {
+                self.allocator
+                    .deallocate(self.bucket_array, (self.bucket_count + 1) as usize)
+            };
+        }
+        self.bucket_array = unsafe {
+            std::mem::transmute::<*const usize, *mut *mut Node<K, V, C>>(EMPTY_BUCKET_ARR.as_ptr())
+        };
+        self.bucket_count = 1;
+    }
+
     /// Frees all buckets
     fn free_buckets(&mut self) {
         if self.bucket_count > 1 {
@@ -352,14 +747,16 @@ impl<K: PartialEq, V, A: Allocator, H: Hash<K>, E: Equals<K>> HashTable<K, V, A,
     /// # Arguments
     ///
     /// `target_bucket`: The target hash bucket, calculated with the hash.  
+    /// `hash`: The hash of the key, used to find the bucket again after a re-hash.  
     /// `key`: The key of the K-V pair.  
     /// `value`: The value of the K-V pair.  
     fn insert_impl(
         &mut self,
-        mut target_bucket: *mut *mut Node<K, V>,
+        mut target_bucket: *mut *mut Node<K, V, C>,
+        hash: usize,
         key: K,
         value: V,
-    ) -> &mut Node<K, V> {
+    ) -> &mut Node<K, V, C> {
         // check if we need to re-hash
         if let Some(bucket_count) =
             self.rehash_policy
@@ -367,12 +764,12 @@ impl<K: PartialEq, V, A: Allocator, H: Hash<K>, E: Equals<K>> HashTable<K, V, A,
         {
             self.rehash(bucket_count);
             // update the target bucket
-            target_bucket = self.bucket_for_key_mut(&key);
+            target_bucket = self.bucket_for_hash_mut(hash);
         }
         // allocate a new node and add it to the bucket
-        let node = self.allocator.allocate::<Node<K, V>>(1);
+        let node = self.allocator.allocate::<Node<K, V, C>>(1);
         unsafe {
-            std::ptr::write(node, Node::<K, V>::new(key, value, target_bucket.read()));
+            std::ptr::write(node, Node::new(key, value, target_bucket.read(), hash));
             target_bucket.write(node);
         };
         self.element_count += 1;
@@ -403,21 +800,51 @@ impl<K: PartialEq, V, A: Allocator, H: Hash<K>, E: Equals<K>> HashTable<K, V, A,
             .for_each(|bucket_node_ref| {
                 let mut bucket_node = *bucket_node_ref;
                 while let Some(node) = unsafe { bucket_node.as_mut() } {
-                    let new_index = Self::bucket_index(bucket_count, node.key());
+                    // cached hashes only need to be re-bucketed
+                    let new_index = match node.hash_code.get() {
+                        Some(hash) => hash % bucket_count as usize,
+                        None => Self::bucket_index(bucket_count, node.key()),
+                    };
                     let next_node = node.next;
                     node.next = new_buckets[new_index];
-                    new_buckets[new_index] = node as *mut Node<K, V>;
+                    new_buckets[new_index] = node as *mut Node<K, V, C>;
                     bucket_node = next_node;
                 }
                 *bucket_node_ref = std::ptr::null_mut();
             });
         // free the old buckets before setting new ones
-        self.free_buckets();
+        self.free_bucket_array();
         self.bucket_array = new_buckets.as_mut_ptr();
         self.bucket_count = bucket_count;
     }
 }
 
+impl<
+        K: PartialEq + Clone,
+        V: Clone,
+        A: Allocator + Clone,
+        H: Hash<K>,
+        E: Equals<K>,
+        C: HashCode,
+    > Clone for HashTable<K, V, A, H, E, C>
+{
+    fn clone(&self) -> Self {
+        let mut ht = unsafe { Self::new_in(self.allocator.clone()) };
+        ht.clone_from(self);
+        ht
+    }
+
+    fn clone_from(&mut self, source: &Self) {
+        // clearing keeps the bucket array, so it is only re-allocated if it
+        // can't hold the source
+        self.clear();
+        self.reserve(source.len());
+        for (k, v) in source.iter() {
+            self.insert(k.clone(), v.clone());
+        }
+    }
+}
+
 impl<K: PartialEq, V, A: Allocator + Default> Default
     for HashTable<K, V, A, DefaultHash<K>, EqualTo<K>>
 where
@@ -428,9 +855,12 @@ where
     }
 }
 
-impl<K: PartialEq, V, A: Allocator, H: Hash<K>, E: Equals<K>> Drop for HashTable<K, V, A, H, E> {
+impl<K: PartialEq, V, A: Allocator, H: Hash<K>, E: Equals<K>, C: HashCode> Drop
+    for HashTable<K, V, A, H, E, C>
+{
     fn drop(&mut self) {
         self.free_buckets();
+        self.free_bucket_array();
     }
 }
 
@@ -448,12 +878,12 @@ where
     }
 }
 
-unsafe impl<K: PartialEq + Send, V: Send, A: Allocator + Send, H: Hash<K>, E: Equals<K>> Send
-    for HashTable<K, V, A, H, E>
+unsafe impl<K: PartialEq + Send, V: Send, A: Allocator + Send, H: Hash<K>, E: Equals<K>, C: HashCode>
+    Send for HashTable<K, V, A, H, E, C>
 {
 }
-unsafe impl<K: PartialEq + Sync, V: Sync, A: Allocator + Sync, H: Hash<K>, E: Equals<K>> Sync
-    for HashTable<K, V, A, H, E>
+unsafe impl<K: PartialEq + Sync, V: Sync, A: Allocator + Sync, H: Hash<K>, E: Equals<K>, C: HashCode>
+    Sync for HashTable<K, V, A, H, E, C>
 {
 }
 
@@ -462,8 +892,27 @@ mod test {
 
     use memoffset::offset_of;
 
-    use crate::hash::{DefaultHash, Hash};
-    use crate::internal::hash_table::DefaultHashTable;
+    use crate::allocator::{CountingAllocator, DefaultAllocator};
+    use crate::equals::EqualTo;
+    use crate::hash::{CachedHashCode, CountingHash, DefaultHash, Hash, HashCode, NoHashCode};
+    use crate::internal::hash_table::node::Node;
+    use crate::internal::hash_table::{DefaultHashTable, HashTable};
+
+    /// Inserts `0..100` into a table which starts with no buckets, returning the
+    /// number of hash calls made while re-hashing and the final bucket count
+    fn count_rehash_hashes<C: HashCode>() -> (usize, usize) {
+        let mut ht: HashTable<u32, u32, DefaultAllocator, CountingHash, EqualTo<u32>, C> =
+            unsafe { HashTable::new_in(DefaultAllocator::default()) };
+        CountingHash::reset();
+        for n in 0..100 {
+            ht.insert(n, n);
+        }
+        // each insertion hashes its key once
+        let rehash_hashes = CountingHash::hashes() - 100;
+
+        assert!((0..100).all(|n| ht.get(&n) == Some((&n, &n))));
+        (rehash_hashes, ht.bucket_count())
+    }
 
     #[test]
     fn layout() {
@@ -615,4 +1064,147 @@ mod test {
             assert_eq!(ht.get(&A { a: i }), Some((&A { a: i }, &i)));
         }
     }
+
+    #[test]
+    fn bucket_iter() {
+        let ht: DefaultHashTable<A, u32> = (0..11).map(|n| (A { a: n }, n)).collect();
+        assert_eq!(ht.bucket_len(&A { a: 0 }), 11);
+
+        assert!(ht.bucket_count() > 1);
+        // everything hashes to 1, so every other bucket is empty
+        assert_eq!(ht.bucket_iter(1).count(), 11);
+        assert!(ht.bucket_iter(1).all(|(k, v)| k.a == *v));
+        assert!((0..ht.bucket_count())
+            .filter(|index| *index != 1)
+            .all(|index| ht.bucket_iter(index).next().is_none()));
+    }
+
+    #[test]
+    #[should_panic(expected = "capacity overflow")]
+    fn reserve_beyond_u32() {
+        let mut ht = DefaultHashTable::<u32, u32>::new();
+        ht.reserve((1 << 32) + 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "capacity overflow")]
+    fn reserve_overflowing_count() {
+        let mut ht = DefaultHashTable::new();
+        ht.insert(1u32, 1u32);
+        ht.reserve(u32::MAX as usize);
+    }
+
+    #[test]
+    fn reserve_for_iter_ignores_impossible_hint() {
+        /// An iterator claiming far more elements than it yields
+        struct Liar(u32);
+
+        impl Iterator for Liar {
+            type Item = (u32, u32);
+
+            fn next(&mut self) -> Option<Self::Item> {
+                self.0.checked_sub(1).map(|n| {
+                    self.0 = n;
+                    (n, n)
+                })
+            }
+
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                (usize::MAX, None)
+            }
+        }
+
+        let mut ht = DefaultHashTable::new();
+        ht.insert(10u32, 10u32);
+        let liar = Liar(3);
+        ht.reserve_for_iter(liar.size_hint().0);
+        liar.for_each(|(k, v)| {
+            ht.insert(k, v);
+        });
+        assert_eq!(ht.len(), 4);
+        assert!(ht.bucket_count() < 100);
+    }
+
+    #[test]
+    fn with_bucket_count() {
+        for (requested, expected) in [(0, 1), (1, 2), (2, 2), (50, 53), (97, 97), (98, 103)] {
+            let ht = DefaultHashTable::<u32, u32>::with_bucket_count(requested);
+            assert_eq!(ht.bucket_count(), expected);
+            assert!(ht.is_empty());
+        }
+
+        let mut ht = DefaultHashTable::with_bucket_count(100);
+        for n in 0..100u32 {
+            ht.insert(n, n);
+        }
+        // the table was already big enough, so every key got its own bucket
+        assert_eq!(ht.bucket_count(), 103);
+        assert!((0..100).all(|n| ht.bucket_len(&n) == 1));
+    }
+
+    #[test]
+    fn frees_bucket_arrays() {
+        let mut ht: HashTable<u32, u32, CountingAllocator> = HashTable::new();
+        let allocator = ht.allocator.clone();
+        for n in 0..100 {
+            ht.insert(n, n);
+        }
+
+        ht.clear();
+        assert!(allocator.live_bytes() > 0);
+        std::mem::drop(ht);
+        assert_eq!(allocator.live_bytes(), 0);
+    }
+
+    #[test]
+    fn node_ptr_stable() {
+        let mut ht = DefaultHashTable::with_bucket_count(20);
+        ht.insert(1u32, 10u32);
+        let node = ht.get_node_ptr(&1).unwrap();
+        assert!(ht.get_node_ptr(&2).is_none());
+
+        for n in 2..10 {
+            ht.insert(n, n * 10);
+        }
+        assert_eq!(ht.bucket_count(), 23);
+        assert_eq!(ht.get_node_ptr(&1), Some(node));
+        assert_eq!(unsafe { (*node).key() }, &1);
+
+        // re-hashing relinks the nodes without moving them
+        for n in 10..100 {
+            ht.insert(n, n * 10);
+        }
+        assert!(ht.bucket_count() > 23);
+        assert_eq!(unsafe { (*node).value() }, &10);
+
+        unsafe { (*ht.get_node_ptr_mut(&1).unwrap()).val = 11 };
+        assert_eq!(ht.get(&1), Some((&1, &11)));
+    }
+
+    #[test]
+    fn node_layout() {
+        assert_eq!(
+            std::mem::size_of::<Node<u32, u32, NoHashCode>>(),
+            std::mem::size_of::<usize>() * 2
+        );
+        assert_eq!(
+            std::mem::size_of::<Node<u32, u32, CachedHashCode>>(),
+            std::mem::size_of::<usize>() * 3
+        );
+        assert_eq!(
+            memoffset::offset_of!(Node<u32, u32, CachedHashCode>, hash_code),
+            std::mem::size_of::<usize>() * 2
+        );
+    }
+
+    #[test]
+    fn rehash_cached_hash() {
+        let (rehash_hashes, bucket_count) = count_rehash_hashes::<CachedHashCode>();
+        assert!(bucket_count > 100);
+        assert_eq!(rehash_hashes, 0);
+
+        // without the cache, every re-hash hashes every node again
+        let (rehash_hashes, _) = count_rehash_hashes::<NoHashCode>();
+        assert!(rehash_hashes > 100);
+    }
 }
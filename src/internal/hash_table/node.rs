@@ -1,12 +1,16 @@
-/// A node in a hashtable
+use crate::hash::{HashCode, NoHashCode};
+
+/// A node in a hashtable. `NoHashCode` is zero-sized,
+/// so only nodes which cache their hash grow
 #[repr(C)]
-pub struct Node<K: PartialEq, V> {
+pub struct Node<K: PartialEq, V, C = NoHashCode> {
     pub key: K,
     pub val: V,
     pub next: *mut Self,
+    pub hash_code: C,
 }
 
-impl<K: PartialEq, V> Node<K, V> {
+impl<K: PartialEq, V, C: HashCode> Node<K, V, C> {
     /// Creates the node from a key/value
     ///
     /// # Arguments
@@ -16,14 +20,19 @@ impl<K: PartialEq, V> Node<K, V> {
     /// `val`: The value
     ///
     /// `next`: The next linked node in the list
-    pub fn new(key: K, value: V, next: *mut Self) -> Self {
+    ///
+    /// `hash`: The hash of the key
+    pub fn new(key: K, value: V, next: *mut Self, hash: usize) -> Self {
         Self {
             key,
             val: value,
             next,
+            hash_code: C::new(hash),
         }
     }
+}
 
+impl<K: PartialEq, V, C> Node<K, V, C> {
     /// Returns the key of the node
     pub fn key(&self) -> &K {
         &self.key
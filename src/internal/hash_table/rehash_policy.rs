@@ -48,6 +48,23 @@ const PRIMES: [u32; 257] = [
 ];
 
 impl PrimeRehashPolicy {
+    /// Returns the maximum ratio of elements to buckets before a rehash is needed
+    pub fn max_load_factor(&self) -> f32 {
+        self.max_load_factor
+    }
+
+    /// Returns the smallest prime bucket count which is at least the requested
+    /// bucket count, and schedules the next re-hash for it
+    ///
+    /// # Arguments
+    ///
+    /// `bucket_count`: The requested number of buckets
+    pub fn get_bucket_count(&mut self, bucket_count: u32) -> u32 {
+        let next_prime = PRIMES[PRIMES.lower_bound(&bucket_count).min(PRIMES.len() - 1)];
+        self.next_resize = (next_prime as f32 * self.max_load_factor).ceil() as u32;
+        next_prime
+    }
+
     /// Returns the re-hash that is required given
     /// the addition of new elements
     ///
@@ -1,17 +1,19 @@
 use crate::internal::rb_tree::node::Node;
 use std::marker::PhantomData;
-use std::ptr;
 
 /// An iterator over a Red-Black tree's nodes.
 pub struct Iter<'a, K, V> {
     pub(super) node: *const Node<K, V>,
-    pub(super) anchor: *const (),
+    /// The number of nodes left to visit. We never advance past the last
+    /// node, since the root node's parent isn't part of the tree
+    pub(super) remaining: usize,
     pub(super) _marker: PhantomData<&'a ()>,
 }
 
 pub struct IterMut<'a, K, V> {
     pub(super) node: *mut Node<K, V>,
-    pub(super) anchor: *const (),
+    /// The number of nodes left to visit
+    pub(super) remaining: usize,
     pub(super) _marker: PhantomData<&'a mut ()>,
 }
 
@@ -19,13 +21,16 @@ impl<'a, K: 'a, V: 'a> Iterator for Iter<'a, K, V> {
     type Item = (&'a K, &'a V);
 
     fn next(&mut self) -> Option<Self::Item> {
-        if ptr::eq(self.node, self.anchor as *const _) {
+        if self.remaining == 0 {
             return None;
         }
 
         unsafe { self.node.as_ref() }.map(|node| {
             // update the iterator
-            self.node = node.next();
+            self.remaining -= 1;
+            if self.remaining != 0 {
+                self.node = node.next();
+            }
             (node.key(), node.val())
         })
     }
@@ -35,13 +40,16 @@ impl<'a, K: 'a, V: 'a> Iterator for IterMut<'a, K, V> {
     type Item = (&'a K, &'a mut V);
 
     fn next(&mut self) -> Option<Self::Item> {
-        if ptr::eq(self.node, self.anchor as *const _) {
+        if self.remaining == 0 {
             return None;
         }
 
         unsafe { self.node.as_mut() }.map(|node| {
             // update the iterator
-            self.node = node.next_mut();
+            self.remaining -= 1;
+            if self.remaining != 0 {
+                self.node = node.next_mut();
+            }
             (&node.pair.0, &mut node.pair.1)
         })
     }
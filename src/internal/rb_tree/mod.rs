@@ -4,13 +4,19 @@ use crate::{
     compare::{Compare, Less},
 };
 use duplicate::duplicate_item;
+use std::borrow::Borrow;
+use std::cmp::Ordering;
 use std::marker::PhantomData;
+use std::ptr;
 
-use self::node::Node;
+use self::node::{Color, Node, ParentColor};
 
 pub mod iter;
 pub(crate) mod node;
 
+/// The parent of a node to be inserted, and whether it will be the left child
+type InsertionPosition<K, V> = (*mut Node<K, V>, bool);
+
 #[repr(C)]
 pub struct RBTree<K: PartialEq, V, A: Allocator, C: Compare<K> = Less<K>> {
     /// A 1-size functor in C++
@@ -18,7 +24,10 @@ pub struct RBTree<K: PartialEq, V, A: Allocator, C: Compare<K> = Less<K>> {
     /// Real EASTL uses a node without a K/V pair
     /// here, but that would mean we would need some
     /// base node as well. Splitting them up also
-    /// just makes sense
+    /// just makes sense. Like EASTL, the parent of the
+    /// root and the bounds of an empty tree are this
+    /// anchor, so moving a tree invalidates them until
+    /// the next insertion or removal re-links it
     begin: *mut Node<K, V>,
     end: *mut Node<K, V>,
     parent: *mut Node<K, V>,
@@ -40,6 +49,114 @@ impl<K: PartialEq, V, A: Allocator + Default, C: Compare<K> + Default> RBTree<K,
     }
 }
 
+impl<K: PartialEq, V, A: Allocator + Default, C: Compare<K> + Default> RBTree<K, V, A, C> {
+    /// Builds a red-black tree from key-value pairs in linear time. The keys
+    /// must be strictly increasing according to the comparator
+    ///
+    /// # Arguments
+    ///
+    /// `iter`: The sorted key-value pairs
+    pub fn from_sorted_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        Self::new().link_sorted(iter)
+    }
+}
+
+impl<K: PartialEq, V, A: Allocator, C: Compare<K>> RBTree<K, V, A, C> {
+    /// Links key-value pairs into the empty tree as a balanced tree in linear
+    /// time. The keys must be strictly increasing according to the comparator
+    ///
+    /// # Arguments
+    ///
+    /// `iter`: The sorted key-value pairs
+    fn link_sorted<I: IntoIterator<Item = (K, V)>>(mut self, iter: I) -> Self {
+        debug_assert!(self.is_empty());
+        // collect the pairs before allocating any nodes, so that a panicking iterator (like a
+        // panicking clone) drops the pairs rather than leaking nodes
+        let pairs = iter.into_iter().collect::<Vec<_>>();
+        debug_assert!(
+            pairs.windows(2).all(|w| C::compare(&w[0].0, &w[1].0)),
+            "keys must be strictly increasing"
+        );
+        let nodes = pairs
+            .into_iter()
+            .map(|pair| {
+                let node = self.allocator.allocate::<Node<K, V>>(1);
+                unsafe {
+                    node.write(Node {
+                        right: std::ptr::null_mut(),
+                        left: std::ptr::null_mut(),
+                        parent: ParentColor::default(),
+                        pair,
+                    })
+                };
+                node
+            })
+            .collect::<Vec<_>>();
+
+        if let (Some(&first), Some(&last)) = (nodes.first(), nodes.last()) {
+            // leaves are either on the bottom level or the one above it. making only the bottom
+            // level red keeps the black height the same along every path
+            let red_depth = (usize::BITS - 1 - nodes.len().leading_zeros()) as usize;
+            let anchor = self.anchor();
+            self.parent = unsafe { Self::build_balanced(&nodes, anchor, 0, red_depth) };
+            self.begin = first;
+            self.end = last;
+            self.size = nodes.len() as u32;
+        }
+        self.link_anchor();
+
+        self
+    }
+
+    /// Links sorted nodes into a perfectly balanced subtree, returning its root
+    ///
+    /// # Arguments
+    ///
+    /// `nodes`: The sorted nodes in the subtree
+    ///
+    /// `parent`: The parent of the subtree's root
+    ///
+    /// `depth`: The depth of the subtree's root
+    ///
+    /// `red_depth`: The depth at which nodes are colored red
+    ///
+    /// # Safety
+    ///
+    /// All of the nodes must be valid and not yet linked into a tree
+    unsafe fn build_balanced(
+        nodes: &[*mut Node<K, V>],
+        parent: *mut Node<K, V>,
+        depth: usize,
+        red_depth: usize,
+    ) -> *mut Node<K, V> {
+        if nodes.is_empty() {
+            return std::ptr::null_mut();
+        }
+
+        let mid = nodes.len() / 2;
+        let node = nodes[mid];
+        let color = if depth != 0 && depth == red_depth {
+            Color::Red
+        } else {
+            Color::Black
+        };
+        (*node).parent = ParentColor::_new(color, parent);
+        (*node).left = Self::build_balanced(&nodes[..mid], node, depth + 1, red_depth);
+        (*node).right = Self::build_balanced(&nodes[mid + 1..], node, depth + 1, red_depth);
+        node
+    }
+}
+
+impl<K: PartialEq + Clone, V: Clone, A: Allocator + Clone, C: Compare<K> + Clone> Clone
+    for RBTree<K, V, A, C>
+{
+    fn clone(&self) -> Self {
+        // the source is already sorted, so the copy can be built in linear time
+        Self::with_allocator_and_compare(self.allocator.clone(), self.compare.clone())
+            .link_sorted(self.iter().map(|(k, v)| (k.clone(), v.clone())))
+    }
+}
+
 impl<K: PartialEq, V, A: Allocator + Default, C: Compare<K> + Default> Default
     for RBTree<K, V, A, C>
 {
@@ -121,7 +238,11 @@ impl<K: PartialEq, V, A: Allocator, C: Compare<K>> RBTree<K, V, A, C> {
     /// # Arguments
     ///
     /// `key`: The key to index the pair
-    pub fn contains_key(&self, key: &K) -> bool {
+    pub fn contains_key<Q: ?Sized>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        C: Compare<Q>,
+    {
         self.get(key).is_some()
     }
 
@@ -130,29 +251,77 @@ impl<K: PartialEq, V, A: Allocator, C: Compare<K>> RBTree<K, V, A, C> {
     /// # Arguments
     ///
     /// `key`: The key to index the pair
-    pub fn get(&self, key: &K) -> Option<&V> {
+    pub fn get<Q: ?Sized>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        C: Compare<Q>,
+    {
         self.find_in_tree(key).map(|n| n.val())
     }
 
+    /// Fetches the stored key and the value indexed by the key in the
+    /// tree. The stored key may differ from `key` if the comparator
+    /// considers them equal
+    ///
+    /// # Arguments
+    ///
+    /// `key`: The key to index the pair
+    pub fn get_key_value<Q: ?Sized>(&self, key: &Q) -> Option<(&K, &V)>
+    where
+        K: Borrow<Q>,
+        C: Compare<Q>,
+    {
+        self.find_in_tree(key).map(|n| (n.key(), n.val()))
+    }
+
     /// Fetches the value indexed by the key in the tree
     ///
     /// # Arguments
     ///
     /// `key`: The key to index the pair
-    pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+    pub fn get_mut<Q: ?Sized>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        C: Compare<Q>,
+    {
         self.find_in_tree(key).map(|n| n.val_mut())
     }
 
-    /// Inserts a key-value pair into the red-black tree
+    /// Fetches the value indexed by the key in the tree, inserting the
+    /// value produced by `f` if the key isn't present
+    ///
+    /// # Arguments
+    ///
+    /// `key`: The key to index the pair
+    ///
+    /// `f`: A function producing the value to insert
+    pub fn get_or_insert_with<F: FnOnce() -> V>(&mut self, key: K, f: F) -> &mut V {
+        let node = match self.find_insertion_position(&key) {
+            Ok(existing_node) => existing_node,
+            Err((parent, is_left)) => self.insert_node(parent, is_left, key, f()),
+        };
+        unsafe { (*node).val_mut() }
+    }
+
+    /// Inserts a key-value pair into the red-black tree, returning the
+    /// old value if the key was already present
     ///
     /// # Arguments
     ///
     /// `key`: The key to insert and index by
     ///
     /// `value`: The value to insert
-    pub fn _insert(&mut self, key: K, _value: V) -> Option<V> {
-        let _insertion_position = self._find_insertion_position(&key);
-        unimplemented!()
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        match self.find_insertion_position(&key) {
+            Ok(existing_node) => Some(std::mem::replace(
+                unsafe { (*existing_node).val_mut() },
+                value,
+            )),
+            Err((parent, is_left)) => {
+                self.insert_node(parent, is_left, key, value);
+                None
+            }
+        }
     }
 
     /// Returns true if the red-black tree contains no elements
@@ -160,20 +329,17 @@ impl<K: PartialEq, V, A: Allocator, C: Compare<K>> RBTree<K, V, A, C> {
         self.len() == 0
     }
 
-    /// Returns an iterator over the elements in the tree.
-    ///
-    /// # Safety
-    /// This iterator is not tested as trees are only partially implemented.
+    /// Returns an iterator over the elements in the tree, in order
     #[duplicate_item(
         iter        Self        Iter;
         [iter]      [&Self]     [Iter];
         [iter_mut]  [&mut Self] [IterMut];
     )]
     #[allow(clippy::needless_arbitrary_self_type)]
-    pub unsafe fn iter(self: Self) -> Iter<K, V> {
+    pub fn iter(self: Self) -> Iter<'_, K, V> {
         Iter {
             node: self.begin,
-            anchor: &self.begin as *const _ as *const _,
+            remaining: self.len(),
             _marker: PhantomData,
         }
     }
@@ -189,7 +355,11 @@ impl<K: PartialEq, V, A: Allocator, C: Compare<K>> RBTree<K, V, A, C> {
     /// # Arguments
     ///
     /// `key`: The key to index the pair
-    pub fn _remove(&mut self, key: &K) -> Option<V> {
+    pub fn remove<Q: ?Sized>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        C: Compare<Q>,
+    {
         self.remove_entry(key).map(|(_, val)| val)
     }
 
@@ -199,8 +369,87 @@ impl<K: PartialEq, V, A: Allocator, C: Compare<K>> RBTree<K, V, A, C> {
     /// # Arguments
     ///
     /// `key`: The key to index the pair
-    pub fn remove_entry(&mut self, _key: &K) -> Option<(K, V)> {
-        unimplemented!()
+    pub fn remove_entry<Q: ?Sized>(&mut self, key: &Q) -> Option<(K, V)>
+    where
+        K: Borrow<Q>,
+        C: Compare<Q>,
+    {
+        let node = self.find_in_tree(key)? as *mut Node<K, V>;
+        unsafe {
+            self.remove_node(node);
+            let pair = ptr::read(&(*node).pair);
+            self.allocator.deallocate(node, 1);
+            Some(pair)
+        }
+    }
+
+    /// Checks the red-black invariants of the tree: the root is black, no red
+    /// node has a red child, every path has the same number of black nodes,
+    /// and the keys are in order
+    #[cfg(any(test, debug_assertions))]
+    pub fn validate(&self) -> Result<(), &'static str> {
+        if let Some(root) = self.parent() {
+            if root._color() != Color::Black {
+                return Err("root is not black");
+            }
+        }
+        unsafe { Self::validate_subtree(self.parent) }?;
+
+        let mut keys = self.iter().map(|(key, _)| key);
+        if let Some(first) = keys.next() {
+            keys.try_fold(first, |previous, key| {
+                if C::compare(previous, key) {
+                    Ok(key)
+                } else {
+                    Err("keys are out of order")
+                }
+            })?;
+        }
+
+        if self.iter().count() != self.len() {
+            return Err("size does not match the number of nodes");
+        }
+        // the bounds of an empty tree are the anchor, which may be stale if the tree moved
+        let (lowest, highest) =
+            unsafe { (Self::leftmost(self.parent), Self::rightmost(self.parent)) };
+        if !self.is_empty() && (lowest != self.begin || highest != self.end) {
+            return Err("begin or end is not the lowest or highest node");
+        }
+
+        Ok(())
+    }
+
+    /// Checks the red-black invariants of a subtree, returning its black height
+    ///
+    /// # Arguments
+    ///
+    /// `node`: The root of the subtree, or null
+    ///
+    /// # Safety
+    ///
+    /// `node` must be null or a valid node in the tree
+    #[cfg(any(test, debug_assertions))]
+    unsafe fn validate_subtree(node: *mut Node<K, V>) -> Result<usize, &'static str> {
+        let Some(node) = node.as_ref() else {
+            return Ok(1);
+        };
+
+        for child in [node.left, node.right] {
+            if let Some(child) = child.as_ref() {
+                if !ptr::eq(child.parent.ptr(), node) {
+                    return Err("child does not point to its parent");
+                }
+                if node._color() == Color::Red && child._color() == Color::Red {
+                    return Err("red node has a red child");
+                }
+            }
+        }
+
+        let left_height = Self::validate_subtree(node.left)?;
+        if left_height != Self::validate_subtree(node.right)? {
+            return Err("black height differs between paths");
+        }
+        Ok(left_height + (node._color() == Color::Black) as usize)
     }
 
     /// Finds the node in the tree given the head and key
@@ -210,41 +459,424 @@ impl<K: PartialEq, V, A: Allocator, C: Compare<K>> RBTree<K, V, A, C> {
     /// `head`: The head node of the tree
     ///
     /// `key`: The key to index the pair
-    fn find_in_tree(&self, key: &K) -> Option<&mut Node<K, V>> {
+    fn find_in_tree<Q: ?Sized>(&self, key: &Q) -> Option<&mut Node<K, V>>
+    where
+        K: Borrow<Q>,
+        C: Compare<Q>,
+    {
         let mut current_node = self.parent();
         while let Some(node) = current_node {
-            if C::compare(key, node.key()) {
-                current_node = node.left();
-            // if the key !< node and node !< key they must be equal
-            } else if !C::compare(node.key(), key) {
-                return Some(node);
-            } else {
-                current_node = node.right();
+            match C::cmp(key, node.key().borrow()) {
+                Ordering::Less => current_node = node.left(),
+                Ordering::Equal => return Some(node),
+                Ordering::Greater => current_node = node.right(),
             }
         }
         None
     }
 
-    /// Finds the position to insert a new key-value pair
+    /// Finds the node holding the key, or the position to insert a new
+    /// key-value pair as the parent node and whether the pair is its left child
     ///
     /// # Arguments
     ///
     /// `key`: The key to index the pair
-    fn _find_insertion_position(&self, key: &K) -> Option<&mut Node<K, V>> {
+    fn find_insertion_position(&self, key: &K) -> Result<*mut Node<K, V>, InsertionPosition<K, V>> {
         let mut current_node = self.parent;
-        let mut prev_node = std::ptr::null_mut();
-        let mut _is_key_less_than_node = false;
+        let mut parent = std::ptr::null_mut();
+        let mut is_left = false;
         while let Some(node) = unsafe { current_node.as_mut() } {
-            prev_node = current_node;
-            _is_key_less_than_node = C::compare(key, node.key());
-            if _is_key_less_than_node {
-                current_node = node.left;
+            parent = current_node;
+            match C::cmp(key, node.key()) {
+                Ordering::Less => {
+                    is_left = true;
+                    current_node = node.left;
+                }
+                Ordering::Equal => return Ok(current_node),
+                Ordering::Greater => {
+                    is_left = false;
+                    current_node = node.right;
+                }
+            }
+        }
+
+        Err((parent, is_left))
+    }
+
+    /// Inserts a new node into the tree and re-balances it
+    ///
+    /// # Arguments
+    ///
+    /// `parent`: The parent of the new node, or null if the tree is empty. The
+    /// bounds of an empty tree may be null or the anchor, so they aren't used here
+    ///
+    /// `is_left`: True if the new node is the left child of its parent
+    ///
+    /// `key`: The key of the pair
+    ///
+    /// `value`: The value of the pair
+    fn insert_node(
+        &mut self,
+        parent: *mut Node<K, V>,
+        is_left: bool,
+        key: K,
+        value: V,
+    ) -> *mut Node<K, V> {
+        let node = self.allocator.allocate::<Node<K, V>>(1);
+        unsafe {
+            node.write(Node {
+                right: std::ptr::null_mut(),
+                left: std::ptr::null_mut(),
+                parent: ParentColor::_new(Color::Red, parent),
+                pair: (key, value),
+            });
+
+            if let Some(parent) = parent.as_mut() {
+                if is_left {
+                    parent.left = node;
+                } else {
+                    parent.right = node;
+                }
             } else {
-                current_node = node.right;
+                self.parent = node;
             }
         }
 
-        unsafe { prev_node.as_mut() }
+        // keep track of the lowest and highest nodes
+        if parent.is_null() {
+            self.begin = node;
+            self.end = node;
+        } else if is_left && parent == self.begin {
+            self.begin = node;
+        } else if !is_left && parent == self.end {
+            self.end = node;
+        }
+        self.size += 1;
+
+        unsafe { self.rebalance_after_insert(node) };
+        self.link_anchor();
+        node
+    }
+
+    /// Restores the red-black properties after inserting a red node
+    ///
+    /// # Arguments
+    ///
+    /// `node`: The newly inserted node
+    ///
+    /// # Safety
+    ///
+    /// `node` must be a valid node in the tree
+    unsafe fn rebalance_after_insert(&mut self, mut node: *mut Node<K, V>) {
+        // the root's parent is the anchor, which must never be treated as a node
+        while !ptr::eq(node, self.parent) {
+            let parent = &mut *(*node).parent.ptr();
+            if parent._color() == Color::Black {
+                break;
+            }
+            // the parent is red, so it can't be the root and the grandparent must exist
+            let grandparent = parent.parent.ptr();
+            let parent_is_left = ptr::eq(parent, (*grandparent).left);
+            let uncle = if parent_is_left {
+                (*grandparent).right
+            } else {
+                (*grandparent).left
+            };
+
+            if let Some(uncle) = uncle.as_mut().filter(|uncle| uncle._color() == Color::Red) {
+                // push the blackness down from the grandparent and continue from there
+                parent._set_color(Color::Black);
+                uncle._set_color(Color::Black);
+                (*grandparent)._set_color(Color::Red);
+                node = grandparent;
+            } else {
+                // make sure the node is on the outside, then rotate the grandparent
+                if parent_is_left {
+                    if ptr::eq(node, parent.right) {
+                        node = parent;
+                        self.rotate_left(node);
+                    }
+                } else if ptr::eq(node, parent.left) {
+                    node = parent;
+                    self.rotate_right(node);
+                }
+                (*(*node).parent.ptr())._set_color(Color::Black);
+                (*grandparent)._set_color(Color::Red);
+                if parent_is_left {
+                    self.rotate_right(grandparent);
+                } else {
+                    self.rotate_left(grandparent);
+                }
+            }
+        }
+
+        (*self.parent)._set_color(Color::Black);
+    }
+
+    /// Unlinks a node from the tree and re-balances it, without freeing the node
+    ///
+    /// # Arguments
+    ///
+    /// `node`: The node to unlink
+    ///
+    /// # Safety
+    ///
+    /// `node` must be a valid node in the tree
+    unsafe fn remove_node(&mut self, node: *mut Node<K, V>) {
+        // the lowest node has no left child, so its successor is either in its right subtree or
+        // its parent. the same goes for the highest node, mirrored. the root's parent is the
+        // anchor, which `link_anchor` takes care of once the tree is empty
+        if ptr::eq(node, self.begin) {
+            self.begin = Self::leftmost((*node).right);
+            if self.begin.is_null() && !ptr::eq(node, self.parent) {
+                self.begin = (*node).parent.ptr();
+            }
+        }
+        if ptr::eq(node, self.end) {
+            self.end = Self::rightmost((*node).left);
+            if self.end.is_null() && !ptr::eq(node, self.parent) {
+                self.end = (*node).parent.ptr();
+            }
+        }
+        self.size -= 1;
+
+        // the child moving into the removed position, which may be null, and its parent
+        let (child, child_parent, removed_color) =
+            if (*node).left.is_null() || (*node).right.is_null() {
+                let child = if (*node).left.is_null() {
+                    (*node).right
+                } else {
+                    (*node).left
+                };
+                self.replace_child(node, child);
+                (child, (*node).parent.ptr(), (*node)._color())
+            } else {
+                // move the successor into the removed node's position
+                let successor = Self::leftmost((*node).right);
+                let child = (*successor).right;
+                let removed_color = (*successor)._color();
+                let child_parent = if ptr::eq((*successor).parent.ptr(), node) {
+                    successor
+                } else {
+                    let child_parent = (*successor).parent.ptr();
+                    self.replace_child(successor, child);
+                    (*successor)._set_right((*node).right);
+                    child_parent
+                };
+                self.replace_child(node, successor);
+                (*successor)._set_left((*node).left);
+                (*successor)._set_color((*node)._color());
+                (child, child_parent, removed_color)
+            };
+
+        if removed_color == Color::Black {
+            self.rebalance_after_remove(child, child_parent);
+        }
+        self.link_anchor();
+    }
+
+    /// Restores the red-black properties after removing a black node
+    ///
+    /// # Arguments
+    ///
+    /// `node`: The node which took the removed node's position, which may be null
+    ///
+    /// `parent`: The parent of `node`, which is only used if `node` isn't the root
+    ///
+    /// # Safety
+    ///
+    /// `node` must be null or a valid node in the tree, and `parent` must be its parent
+    unsafe fn rebalance_after_remove(
+        &mut self,
+        mut node: *mut Node<K, V>,
+        mut parent: *mut Node<K, V>,
+    ) {
+        // `node` is short one black node on its paths
+        while !ptr::eq(node, self.parent) && Self::is_black(node) {
+            let node_is_left = ptr::eq(node, (*parent).left);
+            // the sibling's subtree has at least one more black node, so it can't be null
+            let mut sibling = if node_is_left {
+                (*parent).right
+            } else {
+                (*parent).left
+            };
+
+            if !Self::is_black(sibling) {
+                // make the sibling black so one of the cases below applies
+                (*sibling)._set_color(Color::Black);
+                (*parent)._set_color(Color::Red);
+                if node_is_left {
+                    self.rotate_left(parent);
+                    sibling = (*parent).right;
+                } else {
+                    self.rotate_right(parent);
+                    sibling = (*parent).left;
+                }
+            }
+
+            let (near_nephew, far_nephew) = if node_is_left {
+                ((*sibling).left, (*sibling).right)
+            } else {
+                ((*sibling).right, (*sibling).left)
+            };
+            if Self::is_black(near_nephew) && Self::is_black(far_nephew) {
+                // remove a black node from the sibling's side and continue from the parent
+                (*sibling)._set_color(Color::Red);
+                node = parent;
+                parent = (*node).parent.ptr();
+            } else {
+                // make sure the red nephew is on the outside, then rotate the parent
+                if Self::is_black(far_nephew) {
+                    (*near_nephew)._set_color(Color::Black);
+                    (*sibling)._set_color(Color::Red);
+                    if node_is_left {
+                        self.rotate_right(sibling);
+                        sibling = (*parent).right;
+                    } else {
+                        self.rotate_left(sibling);
+                        sibling = (*parent).left;
+                    }
+                }
+                (*sibling)._set_color((*parent)._color());
+                (*parent)._set_color(Color::Black);
+                if node_is_left {
+                    (*(*sibling).right)._set_color(Color::Black);
+                    self.rotate_left(parent);
+                } else {
+                    (*(*sibling).left)._set_color(Color::Black);
+                    self.rotate_right(parent);
+                }
+                node = self.parent;
+                break;
+            }
+        }
+
+        if let Some(node) = node.as_mut() {
+            node._set_color(Color::Black);
+        }
+    }
+
+    /// Returns true if the node is black, treating null leaves as black
+    ///
+    /// # Arguments
+    ///
+    /// `node`: The node, or null
+    ///
+    /// # Safety
+    ///
+    /// `node` must be null or a valid node
+    unsafe fn is_black(node: *mut Node<K, V>) -> bool {
+        node.as_ref()
+            .is_none_or(|node| node._color() == Color::Black)
+    }
+
+    /// Returns the lowest node in a subtree, or null if the subtree is empty
+    ///
+    /// # Arguments
+    ///
+    /// `node`: The root of the subtree, or null
+    ///
+    /// # Safety
+    ///
+    /// `node` must be null or a valid node
+    unsafe fn leftmost(mut node: *mut Node<K, V>) -> *mut Node<K, V> {
+        while let Some(left) = node
+            .as_ref()
+            .map(|node| node.left)
+            .filter(|left| !left.is_null())
+        {
+            node = left;
+        }
+        node
+    }
+
+    /// Returns the highest node in a subtree, or null if the subtree is empty
+    ///
+    /// # Arguments
+    ///
+    /// `node`: The root of the subtree, or null
+    ///
+    /// # Safety
+    ///
+    /// `node` must be null or a valid node
+    unsafe fn rightmost(mut node: *mut Node<K, V>) -> *mut Node<K, V> {
+        while let Some(right) = node
+            .as_ref()
+            .map(|node| node.right)
+            .filter(|right| !right.is_null())
+        {
+            node = right;
+        }
+        node
+    }
+
+    /// Rotates a node left, making its right child its parent
+    ///
+    /// # Arguments
+    ///
+    /// `node`: The node to rotate
+    ///
+    /// # Safety
+    ///
+    /// `node` must be a valid node in the tree with a right child
+    unsafe fn rotate_left(&mut self, node: *mut Node<K, V>) {
+        let pivot = (*node).right;
+        (*node).right = (*pivot).left;
+        if let Some(pivot_left) = (*pivot).left.as_mut() {
+            pivot_left._set_parent(node);
+        }
+        self.replace_child(node, pivot);
+        (*pivot).left = node;
+        (*node)._set_parent(pivot);
+    }
+
+    /// Rotates a node right, making its left child its parent
+    ///
+    /// # Arguments
+    ///
+    /// `node`: The node to rotate
+    ///
+    /// # Safety
+    ///
+    /// `node` must be a valid node in the tree with a left child
+    unsafe fn rotate_right(&mut self, node: *mut Node<K, V>) {
+        let pivot = (*node).left;
+        (*node).left = (*pivot).right;
+        if let Some(pivot_right) = (*pivot).right.as_mut() {
+            pivot_right._set_parent(node);
+        }
+        self.replace_child(node, pivot);
+        (*pivot).right = node;
+        (*node)._set_parent(pivot);
+    }
+
+    /// Puts `new_child` in place of `old_child` under the parent of `old_child`
+    ///
+    /// # Arguments
+    ///
+    /// `old_child`: The node being replaced
+    ///
+    /// `new_child`: The replacement node, which may be null
+    ///
+    /// # Safety
+    ///
+    /// `old_child` must be a valid node in the tree, and `new_child` must be null or valid
+    unsafe fn replace_child(&mut self, old_child: *mut Node<K, V>, new_child: *mut Node<K, V>) {
+        let parent = if ptr::eq(old_child, self.parent) {
+            self.parent = new_child;
+            self.anchor()
+        } else {
+            let parent = (*old_child).parent.ptr();
+            if ptr::eq(old_child, (*parent).left) {
+                (*parent).left = new_child;
+            } else {
+                (*parent).right = new_child;
+            }
+            parent
+        };
+        if let Some(new_child) = new_child.as_mut() {
+            new_child._set_parent(parent);
+        }
     }
 
     /// Frees a node and its children
@@ -259,8 +891,11 @@ impl<K: PartialEq, V, A: Allocator, C: Compare<K>> RBTree<K, V, A, C> {
         if let Some(right) = root.right() {
             self.free_tree(right)
         }
-        // deallocate the current node
-        unsafe { self.allocator.deallocate(root, 1) }
+        // drop and deallocate the current node
+        unsafe {
+            std::ptr::drop_in_place(&mut root.pair);
+            self.allocator.deallocate(root, 1)
+        }
     }
 
     /// Frees all of the nodes in the tree
@@ -268,19 +903,43 @@ impl<K: PartialEq, V, A: Allocator, C: Compare<K>> RBTree<K, V, A, C> {
         if let Some(node) = unsafe { self.parent.as_mut() } {
             self.free_tree(node)
         }
+        self.parent = std::ptr::null_mut();
+        self.size = 0;
+        self.link_anchor();
+    }
+
+    /// Returns the anchor: the bounds and root of the tree, laid out like
+    /// a node without a pair. It must never be treated as a real node
+    fn anchor(&self) -> *mut Node<K, V> {
+        &self.begin as *const _ as *mut _
+    }
+
+    /// Points the root's parent at the anchor, or the bounds at the anchor if
+    /// the tree is empty, as EASTL expects. This re-links trees that moved
+    fn link_anchor(&mut self) {
+        let anchor = self.anchor();
+        if let Some(root) = self.parent() {
+            root._set_parent(anchor);
+        } else {
+            self.begin = anchor;
+            self.end = anchor;
+        }
     }
 
     /// Returns the parent node
+    #[allow(clippy::mut_from_ref)]
     fn parent(&self) -> Option<&mut Node<K, V>> {
         unsafe { self.parent.as_mut() }
     }
 
     /// Returns the beginning (lowest) node
+    #[allow(clippy::mut_from_ref)]
     fn _begin(&self) -> Option<&mut Node<K, V>> {
         unsafe { self.begin.as_mut() }
     }
 
     /// Returns the end (highest) node
+    #[allow(clippy::mut_from_ref)]
     fn _end(&self) -> Option<&mut Node<K, V>> {
         unsafe { self.end.as_mut() }
     }
@@ -288,11 +947,13 @@ impl<K: PartialEq, V, A: Allocator, C: Compare<K>> RBTree<K, V, A, C> {
 
 #[cfg(test)]
 mod test {
-    use crate::allocator::DefaultAllocator;
+    use crate::allocator::{CountingAllocator, DefaultAllocator};
     use crate::compare::Less;
     use memoffset::offset_of;
 
+    use super::node::{Color, Node};
     use super::RBTree;
+    use std::ptr;
 
     type DefaultRBTree<K, V, C = Less<K>> = RBTree<K, V, DefaultAllocator, C>;
 
@@ -335,4 +996,161 @@ mod test {
         assert_eq!(rb_tree.len(), 0);
         assert!(rb_tree.is_empty());
     }
+
+    #[test]
+    fn insert_tracks_bounds() {
+        let mut rb_tree = DefaultRBTree::<u32, u32>::default();
+        for key in [5, 3, 8, 1, 9, 4] {
+            rb_tree.insert(key, key);
+        }
+
+        assert_eq!(rb_tree.len(), 6);
+        assert_eq!(unsafe { (*rb_tree.begin).key() }, &1);
+        assert_eq!(unsafe { (*rb_tree.end).key() }, &9);
+        assert_eq!(unsafe { (*rb_tree.parent).parent.ptr() }, rb_tree.anchor());
+
+        rb_tree.clear();
+        assert!(rb_tree.is_empty());
+        assert_eq!(rb_tree.begin, rb_tree.anchor());
+        assert!(rb_tree.iter().next().is_none());
+    }
+
+    #[test]
+    fn clone_panic_frees_nodes() {
+        /// A key which panics when the key 3 is cloned
+        #[derive(PartialEq, PartialOrd)]
+        struct PanicOnClone(u32);
+
+        impl Clone for PanicOnClone {
+            fn clone(&self) -> Self {
+                assert_ne!(self.0, 3, "cloned 3");
+                Self(self.0)
+            }
+        }
+
+        let allocator = CountingAllocator::default();
+        let mut rb_tree = RBTree::<PanicOnClone, u32, _>::with_allocator_and_compare(
+            allocator.clone(),
+            Less::default(),
+        );
+        for n in 0..10 {
+            rb_tree.insert(PanicOnClone(n), n);
+        }
+
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| rb_tree.clone()));
+        assert!(result.is_err());
+        std::mem::drop(rb_tree);
+        assert_eq!(allocator.live_bytes(), 0);
+    }
+
+    #[test]
+    fn from_sorted_iter() {
+        for len in [0, 1, 2, 3, 7, 8, 1000] {
+            let rb_tree: DefaultRBTree<u32, u32> =
+                DefaultRBTree::from_sorted_iter((0..len).map(|n| (n, n)));
+
+            assert_eq!(rb_tree.len(), len as usize);
+            assert!(rb_tree.iter().map(|(k, _)| *k).eq(0..len));
+            assert_eq!(rb_tree.validate(), Ok(()));
+        }
+    }
+
+    #[test]
+    fn insert_remove_validate() {
+        let mut rb_tree = DefaultRBTree::<u32, u32>::default();
+        // insert and remove in scrambled orders to exercise every re-balancing case
+        for key in (0..500).map(|n| (n * 37) % 500) {
+            rb_tree.insert(key, key * 2);
+            assert_eq!(rb_tree.validate(), Ok(()));
+        }
+
+        for key in (0..500).map(|n| (n * 73) % 500).filter(|key| key % 2 == 0) {
+            assert_eq!(rb_tree.remove(&key), Some(key * 2));
+            assert_eq!(rb_tree.validate(), Ok(()));
+        }
+        assert_eq!(rb_tree.len(), 250);
+        assert!(rb_tree.iter().map(|(k, _)| *k).eq((1..500).step_by(2)));
+
+        for key in (1..500).step_by(2).rev() {
+            assert_eq!(rb_tree.remove_entry(&key), Some((key, key * 2)));
+            assert_eq!(rb_tree.validate(), Ok(()));
+        }
+        assert!(rb_tree.is_empty());
+        assert_eq!(rb_tree.begin, rb_tree.anchor());
+        assert_eq!(rb_tree.end, rb_tree.anchor());
+        assert!(rb_tree.parent.is_null());
+    }
+
+    /// Walks the tree the way EASTL's iterator does, from the beginning until the anchor
+    fn eastl_keys(rb_tree: &DefaultRBTree<u32, u32>) -> Vec<u32> {
+        let mut keys = Vec::new();
+        let mut node = rb_tree.begin as *const Node<u32, u32>;
+        while !ptr::eq(node, rb_tree.anchor()) {
+            let current = unsafe { &*node };
+            keys.push(*current.key());
+            node = current.next();
+        }
+        keys
+    }
+
+    #[test]
+    fn eastl_anchor_layout() {
+        // an empty tree constructed by EASTL has its bounds pointing at the anchor
+        let mut rb_tree = DefaultRBTree::<u32, u32>::default();
+        rb_tree.begin = rb_tree.anchor();
+        rb_tree.end = rb_tree.anchor();
+
+        for key in [5, 3, 8, 1, 9, 4, 7, 2, 6, 0] {
+            rb_tree.insert(key, key);
+            assert_eq!(rb_tree.validate(), Ok(()));
+            assert_eq!(unsafe { (*rb_tree.parent).parent.ptr() }, rb_tree.anchor());
+        }
+        assert_eq!(unsafe { (*rb_tree.begin).key() }, &0);
+        assert_eq!(unsafe { (*rb_tree.end).key() }, &9);
+        assert_eq!(eastl_keys(&rb_tree), (0..10).collect::<Vec<_>>());
+
+        for key in [0, 9, 5, 1, 8] {
+            assert_eq!(rb_tree.remove(&key), Some(key));
+            assert_eq!(rb_tree.validate(), Ok(()));
+            assert_eq!(unsafe { (*rb_tree.parent).parent.ptr() }, rb_tree.anchor());
+        }
+        assert_eq!(eastl_keys(&rb_tree), [2, 3, 4, 6, 7]);
+
+        for key in [2, 3, 4, 6, 7] {
+            assert_eq!(rb_tree.remove(&key), Some(key));
+            assert_eq!(rb_tree.validate(), Ok(()));
+        }
+        assert_eq!(rb_tree.begin, rb_tree.anchor());
+        assert_eq!(rb_tree.end, rb_tree.anchor());
+        assert!(rb_tree.parent.is_null());
+        assert!(eastl_keys(&rb_tree).is_empty());
+    }
+
+    #[test]
+    fn moved_tree_relinks_anchor() {
+        let mut rb_tree: DefaultRBTree<u32, u32> =
+            DefaultRBTree::from_sorted_iter((0..10).map(|n| (n, n)));
+        rb_tree.insert(10, 10);
+        assert_eq!(unsafe { (*rb_tree.parent).parent.ptr() }, rb_tree.anchor());
+        assert_eq!(eastl_keys(&rb_tree), (0..11).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn remove_missing() {
+        let mut rb_tree = DefaultRBTree::<u32, u32>::default();
+        assert_eq!(rb_tree.remove(&1), None);
+
+        rb_tree.insert(1, 1);
+        assert_eq!(rb_tree.remove(&2), None);
+        assert_eq!(rb_tree.len(), 1);
+    }
+
+    #[test]
+    fn validate_detects_red_root() {
+        let mut rb_tree = DefaultRBTree::<u32, u32>::default();
+        rb_tree.insert(1, 1);
+        unsafe { (*rb_tree.parent)._set_color(Color::Red) };
+
+        assert_eq!(rb_tree.validate(), Err("root is not black"));
+    }
 }
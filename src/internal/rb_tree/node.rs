@@ -140,6 +140,7 @@ impl<K, V> Node<K, V> {
     }
 
     /// The left child of the node
+    #[allow(clippy::mut_from_ref)]
     pub fn left(&self) -> Option<&mut Self> {
         unsafe { self.left.as_mut() }
     }
@@ -158,6 +159,7 @@ impl<K, V> Node<K, V> {
     }
 
     /// The parent of the node
+    #[allow(clippy::mut_from_ref)]
     pub fn _parent(&self) -> Option<&mut Self> {
         unsafe { self.parent.ptr().as_mut() }
     }
@@ -175,6 +177,7 @@ impl<K, V> Node<K, V> {
     }
 
     /// The right child of the node
+    #[allow(clippy::mut_from_ref)]
     pub fn right(&self) -> Option<&mut Self> {
         unsafe { self.right.as_mut() }
     }
@@ -213,8 +216,9 @@ impl<K, V> Node<K, V> {
         } else {
             // the successor is contained within the ancestors. find the first node that is its
             // parent's left node (meaning the parent is the first node greater than the node)
-            // safety: the parent of a node is always present, because the parent of the root node
-            // is inside the tree itself
+            // safety: the parent of a node is always present as long as this isn't the highest
+            // node. iterators never advance past the highest node, since the root node's parent
+            // may not be part of the tree
             let mut parent = unsafe { &mut *self.parent.ptr() };
             while ptr::eq(self as *const _, parent.right as *const _) {
                 let parent_parent = unsafe { &mut *parent.parent.ptr() };
@@ -16,6 +16,7 @@ pub mod hash_set;
 mod internal;
 pub mod list;
 pub mod map;
+pub mod priority_queue;
 pub mod queue;
 pub mod set;
 pub mod string;
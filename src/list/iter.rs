@@ -39,6 +39,8 @@ impl<'a, T> Iterator for Iter<'a, T> {
     }
 }
 
+impl<'a, T> ExactSizeIterator for Iter<'a, T> {}
+
 /// Iterator over `eastl::List`, yielding mutable references in the list's order
 pub struct IterMut<'a, T: 'a> {
     sentinel_node: *const ListNodeBase,
@@ -76,3 +78,5 @@ impl<'a, T> Iterator for IterMut<'a, T> {
         (self.len, Some(self.len))
     }
 }
+
+impl<'a, T> ExactSizeIterator for IterMut<'a, T> {}
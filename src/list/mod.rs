@@ -2,8 +2,9 @@ use crate::allocator::{Allocator, DefaultAllocator};
 use crate::list::iter::{Iter, IterMut};
 use crate::list::node::{ListNode, ListNodeBase};
 use moveit::{new, New};
+use std::cmp::Ordering;
 use std::marker::PhantomData;
-use std::mem::size_of;
+use std::ptr::null_mut;
 use std::{fmt, ptr};
 
 pub(crate) mod iter;
@@ -81,7 +82,7 @@ impl<T, A: Allocator> List<T, A> {
                 // Drop the value
                 ptr::drop_in_place(&mut (*(to_drop as *mut ListNode<T>)).value);
                 // Deallocate the memory for the node
-                self.allocator.deallocate(to_drop, size_of::<ListNode<T>>());
+                self.allocator.deallocate(to_drop as *mut ListNode<T>, 1);
             }
         }
         self.init_sentinel_node();
@@ -119,6 +120,31 @@ impl<T, A: Allocator> List<T, A> {
         }
     }
 
+    /// Get a reference to the value at `index`, walking the list from the front
+    ///
+    /// # Arguments
+    ///
+    /// `index`: The position of the value
+    ///
+    /// # Return
+    /// A reference to the value if present, `None` if `index` is past the end.
+    pub fn get(&self, index: usize) -> Option<&T> {
+        self.iter().nth(index)
+    }
+
+    /// Get a mutable reference to the value at `index`, walking the list from
+    /// the front
+    ///
+    /// # Arguments
+    ///
+    /// `index`: The position of the value
+    ///
+    /// # Return
+    /// A mutable reference to the value if present, `None` if `index` is past the end.
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        self.iter_mut().nth(index)
+    }
+
     /// Return a forward iterator for this list
     pub fn iter(&self) -> Iter<'_, T> {
         Iter::new(&self.node, self.size())
@@ -167,21 +193,34 @@ impl<T, A: Allocator> List<T, A> {
 
     /// Push a value to the back of the list
     pub fn push_back(&mut self, value: T) {
+        self.push_back_mut(value);
+    }
+
+    /// Push a value to the back of the list, returning a reference to it
+    pub fn push_back_mut(&mut self, value: T) -> &mut T {
+        let new_node = unsafe { self.create_node(value) };
         unsafe {
-            let new_node = self.create_node(value);
             (*new_node).base.insert(&mut self.node);
         }
         self.size += 1;
+
+        unsafe { (*new_node).value_mut() }
     }
 
     /// Push a value to the front of the list
     pub fn push_front(&mut self, value: T) {
+        self.push_front_mut(value);
+    }
+
+    /// Push a value to the front of the list, returning a reference to it
+    pub fn push_front_mut(&mut self, value: T) -> &mut T {
+        let new_node = unsafe { self.create_node(value) };
         unsafe {
-            let new_node = self.create_node(value);
             (*new_node).base.insert(self.node.next);
         }
-
         self.size += 1;
+
+        unsafe { (*new_node).value_mut() }
     }
 
     /// Get the list's size
@@ -189,6 +228,65 @@ impl<T, A: Allocator> List<T, A> {
         self.size as usize
     }
 
+    /// Sorts the list in place with a stable merge sort. Only the links
+    /// between nodes change, so no values are moved or allocated
+    ///
+    /// # Arguments
+    ///
+    /// `compare`: The function used to order the values
+    pub fn sort_by<F: FnMut(&T, &T) -> Ordering>(&mut self, mut compare: F) {
+        if self.size < 2 {
+            return;
+        }
+
+        unsafe {
+            // detach the nodes into a null-terminated chain which only uses `next`
+            (*self.node.prev).next = null_mut();
+            let head = self.node.next;
+            // the guard relinks the nodes into the list once sorting finishes or panics
+            let mut guard = SortGuard {
+                list: self,
+                head,
+                bins: [null_mut(); u32::BITS as usize],
+                left: null_mut(),
+                right: null_mut(),
+                merged: null_mut(),
+                merged_tail: null_mut(),
+            };
+            while !guard.head.is_null() {
+                let mut run = guard.head;
+                guard.head = (*run).next;
+                (*run).next = null_mut();
+
+                let mut bin = 0;
+                while !guard.bins[bin].is_null() {
+                    let earlier = std::mem::replace(&mut guard.bins[bin], null_mut());
+                    run = guard.merge(earlier, run, &mut compare);
+                    bin += 1;
+                }
+                guard.bins[bin] = run;
+            }
+
+            let mut sorted = null_mut();
+            for bin in 0..guard.bins.len() {
+                let run = std::mem::replace(&mut guard.bins[bin], null_mut());
+                if !run.is_null() {
+                    sorted = guard.merge(run, sorted, &mut compare);
+                }
+            }
+            guard.head = sorted;
+        }
+    }
+
+    /// Sorts the list in place with a stable merge sort. Only the links
+    /// between nodes change, so no values are moved or allocated
+    pub fn sort(&mut self)
+    where
+        T: Ord,
+    {
+        self.sort_by(T::cmp)
+    }
+
     // Allocate and initialise a new node
     unsafe fn create_node(&mut self, value: T) -> *mut ListNode<T> {
         let node = unsafe { self.allocator.allocate::<ListNode<T>>(1).as_mut() }.unwrap();
@@ -207,12 +305,118 @@ impl<T, A: Allocator> List<T, A> {
         (*node).remove();
         let value = ptr::read(&(*(node as *mut ListNode<T>)).value);
         // Deallocate the memory for the node
-        self.allocator.deallocate(node, size_of::<ListNode<T>>());
+        self.allocator.deallocate(node as *mut ListNode<T>, 1);
         self.size -= 1;
         value
     }
 }
 
+/// Holds the detached nodes of a list while it is sorted. Every node is
+/// reachable from the guard whenever the comparator runs, so the nodes can be
+/// relinked into the list once sorting finishes or the comparator panics
+struct SortGuard<'a, T, A: Allocator> {
+    list: &'a mut List<T, A>,
+    /// The nodes which haven't been placed into a bin, or the sorted nodes once done
+    head: *mut ListNodeBase,
+    /// `bins[i]` is either empty or holds a sorted run of 2^i nodes. runs in higher bins
+    /// always hold earlier nodes than runs in lower bins
+    bins: [*mut ListNodeBase; u32::BITS as usize],
+    /// The remainders of the chains being merged
+    left: *mut ListNodeBase,
+    right: *mut ListNodeBase,
+    /// The merged prefix of the chains being merged
+    merged: *mut ListNodeBase,
+    merged_tail: *mut ListNodeBase,
+}
+
+impl<'a, T, A: Allocator> SortGuard<'a, T, A> {
+    /// Merges two sorted null-terminated chains, returning the head of the
+    /// merged chain. Nodes from `left` are placed first when values are equal
+    ///
+    /// # Arguments
+    ///
+    /// `left`: The chain holding the earlier nodes
+    ///
+    /// `right`: The chain holding the later nodes
+    ///
+    /// `compare`: The function used to order the values
+    ///
+    /// # Safety
+    ///
+    /// Both chains must consist of valid `ListNode<T>`s which aren't reachable from the guard
+    unsafe fn merge<F: FnMut(&T, &T) -> Ordering>(
+        &mut self,
+        left: *mut ListNodeBase,
+        right: *mut ListNodeBase,
+        compare: &mut F,
+    ) -> *mut ListNodeBase {
+        self.left = left;
+        self.right = right;
+        while !self.left.is_null() && !self.right.is_null() {
+            let left_value = &(*(self.left as *const ListNode<T>)).value;
+            let right_value = &(*(self.right as *const ListNode<T>)).value;
+            let source = if compare(right_value, left_value) == Ordering::Less {
+                &mut self.right
+            } else {
+                &mut self.left
+            };
+            let node = *source;
+            *source = (*node).next;
+
+            if self.merged.is_null() {
+                self.merged = node;
+            } else {
+                (*self.merged_tail).next = node;
+            }
+            self.merged_tail = node;
+        }
+
+        let rest = if self.left.is_null() {
+            self.right
+        } else {
+            self.left
+        };
+        let head = if self.merged.is_null() {
+            rest
+        } else {
+            (*self.merged_tail).next = rest;
+            self.merged
+        };
+        self.left = null_mut();
+        self.right = null_mut();
+        self.merged = null_mut();
+        self.merged_tail = null_mut();
+        head
+    }
+}
+
+impl<'a, T, A: Allocator> Drop for SortGuard<'a, T, A> {
+    fn drop(&mut self) {
+        unsafe {
+            // the merged prefix still links into the chain its tail came from
+            if !self.merged.is_null() {
+                (*self.merged_tail).next = null_mut();
+            }
+
+            // fix up the `prev` links and the sentinel. after a panic the order is unspecified
+            let sentinel: *mut ListNodeBase = &mut self.list.node;
+            let mut prev = sentinel;
+            let chains = [self.head, self.merged, self.left, self.right];
+            for chain in chains.into_iter().chain(self.bins) {
+                let mut current = chain;
+                while !current.is_null() {
+                    (*current).prev = prev;
+                    (*prev).next = current;
+                    prev = current;
+                    current = (*current).next;
+                }
+            }
+            (*prev).next = sentinel;
+            (*sentinel).prev = prev;
+        }
+    }
+}
+
 impl<T, A: Allocator> Drop for List<T, A> {
     fn drop(&mut self) {
         self.clear()
@@ -419,6 +623,56 @@ mod test {
         assert_eq!(last_val, &mut 6u32);
     }
 
+    #[test]
+    fn push_mut() {
+        moveit! {
+            let mut list = unsafe { DefaultList::new() };
+        }
+        *list.push_back_mut(1u32) += 10;
+        let front = list.push_front_mut(2);
+        *front *= 10;
+
+        assert_eq!(list.size(), 2);
+        assert!(list.iter().eq([&20, &11]));
+    }
+
+    #[test]
+    fn get() {
+        moveit! {
+            let mut list = unsafe { DefaultList::new() };
+        }
+        assert_eq!(list.get(0), None);
+        (0..5u32).for_each(|n| list.push_back(n * 10));
+
+        assert_eq!(list.get(0), Some(&0));
+        assert_eq!(list.get(2), Some(&20));
+        assert_eq!(list.get(4), Some(&40));
+        assert_eq!(list.get(5), None);
+
+        *list.get_mut(2).unwrap() = 25;
+        assert_eq!(list.get(2), Some(&25));
+        assert!(list.get_mut(5).is_none());
+    }
+
+    #[test]
+    fn iter_len() {
+        moveit! {
+            let mut list = unsafe { DefaultList::new() };
+        }
+        (0..5u32).for_each(|n| list.push_back(n));
+
+        let mut iter = list.iter();
+        assert_eq!(iter.len(), list.size());
+        for remaining in (0..5).rev() {
+            iter.next();
+            assert_eq!(iter.len(), remaining);
+        }
+
+        let mut iter = list.iter_mut();
+        iter.nth(1);
+        assert_eq!(iter.len(), 3);
+    }
+
     #[test]
     fn pop_front() {
         moveit! {
@@ -452,4 +706,66 @@ mod test {
         assert!(list.empty());
         assert_eq!(list.pop_front(), None);
     }
+
+    #[test]
+    fn sort() {
+        moveit! {
+            let mut list = unsafe { DefaultList::new() };
+        }
+        list.extend([3u32, 1, 4, 1, 5, 9, 2, 6]);
+        list.sort();
+        assert!(list.iter().copied().eq([1, 1, 2, 3, 4, 5, 6, 9]));
+        assert_eq!(list.front(), Some(&1));
+        assert_eq!(list.back(), Some(&9));
+
+        list.sort_by(|a, b| b.cmp(a));
+        assert!(list.iter().copied().eq([9, 6, 5, 4, 3, 2, 1, 1]));
+        assert_eq!(list.pop_back(), Some(1));
+        assert_eq!(list.size(), 7);
+    }
+
+    #[test]
+    fn sort_stable() {
+        moveit! {
+            let mut list = unsafe { DefaultList::new() };
+        }
+        // sort by key only, keeping the insertion index as a payload. equal keys must stay in
+        // insertion order
+        list.extend((0..100u32).map(|n| ((n * 37) % 10, n)));
+        list.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+        let sorted = list.iter().copied().collect::<Vec<_>>();
+        assert_eq!(sorted.len(), 100);
+        assert!(sorted
+            .windows(2)
+            .all(|w| w[0].0 < w[1].0 || (w[0].0 == w[1].0 && w[0].1 < w[1].1)));
+    }
+
+    #[test]
+    fn sort_panic() {
+        moveit! {
+            let mut list = unsafe { DefaultList::new() };
+        }
+        list.extend((0..100u32).map(|n| (n * 37) % 100));
+        let mut comparisons = 0;
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            list.sort_by(|a, b| {
+                comparisons += 1;
+                assert_ne!(comparisons, 200, "comparator panicked");
+                a.cmp(b)
+            })
+        }));
+        assert!(result.is_err());
+
+        // every node is still linked into the list, in both directions
+        assert_eq!(list.size(), 100);
+        let mut values = list.iter().copied().collect::<Vec<_>>();
+        values.sort();
+        assert!(values.into_iter().eq(0..100));
+
+        list.sort();
+        assert!(list.iter().copied().eq(0..100));
+        // the `prev` links are intact too
+        assert!(std::iter::from_fn(|| list.pop_back()).eq((0..100).rev()));
+    }
 }
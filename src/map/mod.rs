@@ -1,17 +1,21 @@
 use crate::internal::rb_tree::iter::{Iter, IterMut};
 use crate::{
-    allocator::Allocator,
+    allocator::{Allocator, DefaultAllocator},
     compare::{Compare, Less},
     internal::rb_tree::RBTree,
 };
 use duplicate::duplicate_item;
+use std::borrow::Borrow;
 use std::fmt::{Debug, Formatter};
 
+/// Map with the default allocator.
+pub type DefaultMap<K, V, C = Less<K>> = Map<K, V, DefaultAllocator, C>;
+
 /// A map backed by a red-black tree that is always ordered.
 /// Insertion, lookup, and removal are O(nlgn). If you do not
 /// need ordering, look at `HashMap`, which takes O(1) time
 /// for those operations
-#[derive(Default)]
+#[derive(Clone, Default)]
 #[repr(C)]
 pub struct Map<K: PartialEq, V, A: Allocator, C: Compare<K> = Less<K>> {
     pub(crate) inner: RBTree<K, V, A, C>,
@@ -43,6 +47,20 @@ impl<K: PartialEq, V, A: Allocator + Default, C: Compare<K>> Map<K, V, A, C> {
     }
 }
 
+impl<K: PartialEq, V, A: Allocator + Default, C: Compare<K> + Default> Map<K, V, A, C> {
+    /// Constructs a balanced map from key-value pairs in linear time. The keys
+    /// must be strictly increasing according to the comparator
+    ///
+    /// # Arguments
+    ///
+    /// `iter`: The sorted key-value pairs
+    pub fn from_sorted_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        Self {
+            inner: RBTree::from_sorted_iter(iter),
+        }
+    }
+}
+
 impl<K: PartialEq, V, A: Allocator, C: Compare<K>> Map<K, V, A, C> {
     /// Constructs a map using a specified allocator
     /// and comparator
@@ -69,7 +87,11 @@ impl<K: PartialEq, V, A: Allocator, C: Compare<K>> Map<K, V, A, C> {
     /// # Arguments
     ///
     /// `key`: The key to index the pair
-    pub fn contains_key(&self, key: &K) -> bool {
+    pub fn contains_key<Q: ?Sized>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        C: Compare<Q>,
+    {
         self.inner.contains_key(key)
     }
 
@@ -78,28 +100,64 @@ impl<K: PartialEq, V, A: Allocator, C: Compare<K>> Map<K, V, A, C> {
     /// # Arguments
     ///
     /// `key`: The key to index the pair
-    pub fn get(&self, key: &K) -> Option<&V> {
+    pub fn get<Q: ?Sized>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        C: Compare<Q>,
+    {
         self.inner.get(key)
     }
 
+    /// Fetches the stored key and the value indexed by the key in the
+    /// map. The stored key may differ from `key` if the comparator
+    /// considers them equal
+    ///
+    /// # Arguments
+    ///
+    /// `key`: The key to index the pair
+    pub fn get_key_value<Q: ?Sized>(&self, key: &Q) -> Option<(&K, &V)>
+    where
+        K: Borrow<Q>,
+        C: Compare<Q>,
+    {
+        self.inner.get_key_value(key)
+    }
+
     /// Fetches the value indexed by the key in the map
     ///
     /// # Arguments
     ///
     /// `key`: The key to index the pair
-    pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+    pub fn get_mut<Q: ?Sized>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        C: Compare<Q>,
+    {
         self.inner.get_mut(key)
     }
 
-    /// Inserts a key-value pair into the map
+    /// Fetches the value indexed by the key in the map, inserting the
+    /// value produced by `f` if the key isn't present
+    ///
+    /// # Arguments
+    ///
+    /// `key`: The key to index the pair
+    ///
+    /// `f`: A function producing the value to insert
+    pub fn get_or_insert_with<F: FnOnce() -> V>(&mut self, key: K, f: F) -> &mut V {
+        self.inner.get_or_insert_with(key, f)
+    }
+
+    /// Inserts a key-value pair into the map, returning the old
+    /// value if the key was already present
     ///
     /// # Arguments
     ///
     /// `key`: The key to insert and index by
     ///
     /// `value`: The value to insert
-    fn _insert(&mut self, key: K, value: V) -> Option<V> {
-        self.inner._insert(key, value)
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        self.inner.insert(key, value)
     }
 
     /// Returns true if the map contains no elements
@@ -107,20 +165,22 @@ impl<K: PartialEq, V, A: Allocator, C: Compare<K>> Map<K, V, A, C> {
         self.inner.is_empty()
     }
 
-    /// Returns an iterator over the elements in the tree.
-    ///
-    /// # Safety
-    /// This iterator is not tested as trees are only partially implemented.
+    /// Returns an iterator over the elements in the map, in order
     #[duplicate_item(
         iter        Self        Iter;
         [iter]      [&Self]     [Iter];
         [iter_mut]  [&mut Self] [IterMut];
     )]
     #[allow(clippy::needless_arbitrary_self_type)]
-    pub unsafe fn iter(self: Self) -> Iter<K, V> {
+    pub fn iter(self: Self) -> Iter<'_, K, V> {
         self.inner.iter()
     }
 
+    /// Returns an iterator over the keys in the map, in order
+    pub fn keys(&self) -> impl Iterator<Item = &K> {
+        self.iter().map(|(k, _)| k)
+    }
+
     /// Returns the number of elements in the map
     pub fn len(&self) -> usize {
         self.inner.len()
@@ -132,9 +192,12 @@ impl<K: PartialEq, V, A: Allocator, C: Compare<K>> Map<K, V, A, C> {
     /// # Arguments
     ///
     /// `key`: The key to index the pair
-
-    fn _remove(&mut self, key: &K) -> Option<V> {
-        self.inner._remove(key)
+    pub fn remove<Q: ?Sized>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        C: Compare<Q>,
+    {
+        self.inner.remove(key)
     }
 
     /// Removes a key-value pair from the map,
@@ -143,9 +206,30 @@ impl<K: PartialEq, V, A: Allocator, C: Compare<K>> Map<K, V, A, C> {
     /// # Arguments
     ///
     /// `key`: The key to index the pair
-    pub fn remove_entry(&mut self, key: &K) -> Option<(K, V)> {
+    pub fn remove_entry<Q: ?Sized>(&mut self, key: &Q) -> Option<(K, V)>
+    where
+        K: Borrow<Q>,
+        C: Compare<Q>,
+    {
         self.inner.remove_entry(key)
     }
+
+    /// Checks that the map's underlying red-black tree is balanced and ordered
+    #[cfg(any(test, debug_assertions))]
+    pub fn validate(&self) -> Result<(), &'static str> {
+        self.inner.validate()
+    }
+
+    /// Returns an iterator over the values in the map, in order of their keys
+    pub fn values(&self) -> impl Iterator<Item = &V> {
+        self.iter().map(|(_, v)| v)
+    }
+
+    /// Returns an iterator over mutable references to the values in the map,
+    /// in order of their keys
+    pub fn values_mut(&mut self) -> impl Iterator<Item = &mut V> {
+        self.iter_mut().map(|(_, v)| v)
+    }
 }
 
 impl<K: PartialEq + Debug, V: Debug, A: Allocator, C: Compare<K>> Debug for Map<K, V, A, C> {
@@ -153,7 +237,7 @@ impl<K: PartialEq + Debug, V: Debug, A: Allocator, C: Compare<K>> Debug for Map<
         write!(
             f,
             "{{{}}}",
-            unsafe { self.iter() }
+            self.iter()
                 .map(|(k, v)| format!("{k:?}: {v:?}"))
                 .collect::<Vec<String>>()
                 .join(",")
@@ -170,3 +254,224 @@ unsafe impl<K: PartialEq + Sync, V: Sync, A: Allocator + Sync, C: Compare<K> + S
     for Map<K, V, A, C>
 {
 }
+
+#[cfg(test)]
+mod test {
+    use crate::compare::Compare;
+    use crate::map::DefaultMap;
+    use std::rc::Rc;
+
+    /// A key which is only compared by its id
+    #[derive(Debug, PartialEq)]
+    struct Interned {
+        id: u32,
+        name: &'static str,
+    }
+
+    struct ById;
+
+    impl Compare<Interned> for ById {
+        fn compare(left: &Interned, right: &Interned) -> bool {
+            left.id < right.id
+        }
+    }
+
+    #[test]
+    fn insert() {
+        let mut map: DefaultMap<_, _> = DefaultMap::default();
+        assert_eq!(map.insert(2, 3), None);
+        assert_eq!(map.insert(1, 2), None);
+        assert_eq!(map.insert(2, 4), Some(3));
+
+        assert_eq!(map.len(), 2);
+        assert_eq!(map.get(&1), Some(&2));
+        assert_eq!(map.get(&2), Some(&4));
+        assert_eq!(map.get(&3), None);
+    }
+
+    #[test]
+    fn get_key_value() {
+        let mut map: DefaultMap<Interned, u32, ById> = DefaultMap::with_compare(ById);
+        map.insert(
+            Interned {
+                id: 1,
+                name: "first",
+            },
+            1,
+        );
+        assert_eq!(
+            map.insert(
+                Interned {
+                    id: 1,
+                    name: "second"
+                },
+                2
+            ),
+            Some(1)
+        );
+
+        let (key, value) = map
+            .get_key_value(&Interned {
+                id: 1,
+                name: "lookup",
+            })
+            .unwrap();
+        assert_eq!(key.name, "first");
+        assert_eq!(*value, 2);
+        assert!(map
+            .get_key_value(&Interned {
+                id: 2,
+                name: "first"
+            })
+            .is_none());
+    }
+
+    #[test]
+    fn iter_ordered() {
+        let mut map: DefaultMap<_, _> = DefaultMap::default();
+        // insert in a scrambled order to exercise the re-balancing
+        for key in (0..100).map(|n| (n * 37) % 100) {
+            map.insert(key, key * 2);
+        }
+
+        assert_eq!(map.len(), 100);
+        assert!(map.iter().map(|(k, _)| *k).eq(0..100));
+        assert!(map.iter().all(|(k, v)| *v == k * 2));
+    }
+
+    #[test]
+    fn remove() {
+        let mut map: DefaultMap<_, _> = DefaultMap::default();
+        for key in (0..100).map(|n| (n * 37) % 100) {
+            map.insert(key, key * 2);
+        }
+
+        for key in (0..100).step_by(3) {
+            assert_eq!(map.remove(&key), Some(key * 2));
+        }
+        assert_eq!(map.remove(&0), None);
+        assert_eq!(map.remove_entry(&1), Some((1, 2)));
+        assert_eq!(map.validate(), Ok(()));
+        assert!(map
+            .iter()
+            .map(|(k, _)| *k)
+            .eq((2..100).filter(|key| key % 3 != 0)));
+    }
+
+    #[test]
+    fn iter_mut() {
+        let mut map: DefaultMap<_, _> = DefaultMap::default();
+        for key in 0..10 {
+            map.insert(key, key);
+        }
+
+        map.iter_mut().for_each(|(_, v)| *v *= 2);
+        assert!(map.iter().all(|(k, v)| *v == k * 2));
+    }
+
+    #[test]
+    fn get_or_insert_with_present() {
+        let mut map: DefaultMap<_, _> = DefaultMap::default();
+        map.insert(1, 2);
+
+        *map.get_or_insert_with(1, || unreachable!()) += 1;
+        assert_eq!(map.get(&1), Some(&3));
+        assert_eq!(map.len(), 1);
+    }
+
+    #[test]
+    fn get_or_insert_with_absent() {
+        let mut map: DefaultMap<_, _> = DefaultMap::default();
+        map.insert(1, 2);
+        let mut calls = 0;
+
+        let val = map.get_or_insert_with(2, || {
+            calls += 1;
+            5
+        });
+        assert_eq!(*val, 5);
+        assert_eq!(calls, 1);
+        assert_eq!(map.len(), 2);
+        assert_eq!(map.get(&2), Some(&5));
+    }
+
+    #[test]
+    fn debug() {
+        let mut map: DefaultMap<_, _> = DefaultMap::default();
+        map.insert(2, 3);
+        map.insert(1, 2);
+
+        assert_eq!(format!("{map:?}"), "{1: 2,2: 3}");
+    }
+
+    #[test]
+    fn from_sorted_iter() {
+        let mut map: DefaultMap<_, _> = DefaultMap::from_sorted_iter((0..100).map(|n| (n, n * 2)));
+
+        assert_eq!(map.len(), 100);
+        assert!(map.iter().map(|(k, _)| *k).eq(0..100));
+        assert_eq!(map.get(&42), Some(&84));
+        assert_eq!(map.insert(100, 200), None);
+        assert!(map.iter().map(|(k, _)| *k).eq(0..101));
+    }
+
+    #[test]
+    fn keys_values() {
+        let mut map: DefaultMap<_, _> = DefaultMap::default();
+        for key in [5, 3, 9, 1, 7] {
+            map.insert(key, key * 10);
+        }
+
+        assert!(map.keys().copied().eq([1, 3, 5, 7, 9]));
+        assert_eq!(map.values().sum::<u32>(), 250);
+        assert!(map.values().copied().eq([10, 30, 50, 70, 90]));
+    }
+
+    #[test]
+    fn values_mut() {
+        let mut map: DefaultMap<_, _> = DefaultMap::default();
+        for key in 0..10 {
+            map.insert(key, key);
+        }
+
+        map.values_mut().for_each(|v| *v *= 2);
+        assert!(map.values().copied().eq((0..10).map(|n| n * 2)));
+        assert!(map.keys().copied().eq(0..10));
+    }
+
+    #[test]
+    fn clone() {
+        let mut map: DefaultMap<_, _> = DefaultMap::default();
+        for key in (0..100).map(|n| (n * 37) % 100) {
+            map.insert(key, key * 2);
+        }
+
+        let mut cloned = map.clone();
+        assert_eq!(cloned.validate(), Ok(()));
+        assert!(cloned.iter().eq(map.iter()));
+
+        // the clone is independent of the source
+        cloned.values_mut().for_each(|v| *v += 1);
+        cloned.remove(&50);
+        assert_eq!(map.len(), 100);
+        assert_eq!(map.get(&50), Some(&100));
+        assert!(map.iter().all(|(k, v)| *v == k * 2));
+        assert!(cloned.iter().all(|(k, v)| *v == k * 2 + 1));
+    }
+
+    #[test]
+    fn clone_drop() {
+        let value = Rc::new(());
+        let mut map: DefaultMap<u32, Rc<()>> = DefaultMap::default();
+        for key in 0..10 {
+            map.insert(key, value.clone());
+        }
+
+        let cloned = map.clone();
+        assert_eq!(Rc::strong_count(&value), 21);
+        std::mem::drop(map);
+        assert_eq!(Rc::strong_count(&value), 11);
+        std::mem::drop(cloned);
+        assert_eq!(Rc::strong_count(&value), 1);
+    }
+}
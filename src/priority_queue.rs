@@ -0,0 +1,226 @@
+use crate::allocator::{Allocator, DefaultAllocator};
+use crate::compare::{Compare, Less};
+use crate::vector::Vector;
+use std::fmt::{Debug, Formatter};
+
+/// Priority queue with the default allocator.
+pub type DefaultPriorityQueue<T, C = Less<T>> = PriorityQueue<T, DefaultAllocator, C>;
+
+/// A binary heap backed by a Vector. The top element is the greatest
+/// according to the comparator, so `Less` produces a max-heap and `Greater`
+/// produces a min-heap
+#[repr(C)]
+pub struct PriorityQueue<T, A: Allocator, C: Compare<T> = Less<T>> {
+    vec: Vector<T, A>,
+    _compare: C,
+}
+
+impl<T, A: Allocator + Default, C: Compare<T> + Default> PriorityQueue<T, A, C> {
+    /// Creates a new empty priority queue
+    pub fn new() -> Self {
+        Self {
+            vec: Vector::new(),
+            _compare: C::default(),
+        }
+    }
+}
+
+impl<T, A: Allocator, C: Compare<T> + Default> PriorityQueue<T, A, C> {
+    /// Creates a new priority queue inside an allocator
+    ///
+    /// # Arguments
+    ///
+    /// `allocator`: The allocator
+    ///
+    /// # Safety
+    ///
+    /// The allocator specified must safely allocate ande de-allocate valid memory
+    pub unsafe fn new_in(allocator: A) -> Self {
+        Self {
+            vec: Vector::new_in(allocator),
+            _compare: C::default(),
+        }
+    }
+}
+
+impl<T, A: Allocator, C: Compare<T>> PriorityQueue<T, A, C> {
+    /// Turns the priority queue into its inner `Vector`, which is in heap order
+    pub fn into_inner(self) -> Vector<T, A> {
+        self.vec
+    }
+
+    /// Returns true if the priority queue contains no elements
+    pub fn is_empty(&self) -> bool {
+        self.vec.is_empty()
+    }
+
+    /// Returns the number of elements in the priority queue
+    pub fn len(&self) -> usize {
+        self.vec.len()
+    }
+
+    /// Peeks the top element in the priority queue without popping it
+    pub fn peek(&self) -> Option<&T> {
+        self.vec.as_slice().first()
+    }
+
+    /// Pops the top element from the priority queue, returning it if there was one
+    pub fn pop(&mut self) -> Option<T> {
+        let len = self.vec.len();
+        if len == 0 {
+            return None;
+        }
+
+        // move the top to the back so it can be popped, then restore the heap
+        self.vec.as_slice_mut().swap(0, len - 1);
+        let top = self.vec.pop();
+        self.sift_down(0);
+        top
+    }
+
+    /// Pushes an element to the priority queue
+    ///
+    /// # Arguments
+    ///
+    /// `elem`: The element
+    pub fn push(&mut self, elem: T) {
+        self.vec.push(elem);
+        self.sift_up(self.vec.len() - 1);
+    }
+
+    /// Restores the heap order for every element, in O(n)
+    fn heapify(&mut self) {
+        // leaves are already valid heaps, so start from the last parent
+        for index in (0..self.vec.len() / 2).rev() {
+            self.sift_down(index);
+        }
+    }
+
+    /// Moves an element down the heap until neither of its children are greater
+    ///
+    /// # Arguments
+    ///
+    /// `index`: The index of the element
+    fn sift_down(&mut self, mut index: usize) {
+        let heap = self.vec.as_slice_mut();
+        loop {
+            let left = index * 2 + 1;
+            if left >= heap.len() {
+                break;
+            }
+
+            // pick the greater of the children
+            let right = left + 1;
+            let child = if right < heap.len() && C::compare(&heap[left], &heap[right]) {
+                right
+            } else {
+                left
+            };
+            if !C::compare(&heap[index], &heap[child]) {
+                break;
+            }
+
+            heap.swap(index, child);
+            index = child;
+        }
+    }
+
+    /// Moves an element up the heap until its parent is not lesser
+    ///
+    /// # Arguments
+    ///
+    /// `index`: The index of the element
+    fn sift_up(&mut self, mut index: usize) {
+        let heap = self.vec.as_slice_mut();
+        while index > 0 {
+            let parent = (index - 1) / 2;
+            if !C::compare(&heap[parent], &heap[index]) {
+                break;
+            }
+
+            heap.swap(parent, index);
+            index = parent;
+        }
+    }
+}
+
+impl<T: Debug, A: Allocator, C: Compare<T>> Debug for PriorityQueue<T, A, C> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.vec.fmt(f)
+    }
+}
+
+impl<T, A: Allocator + Default, C: Compare<T> + Default> Default for PriorityQueue<T, A, C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, A: Allocator + Default, C: Compare<T> + Default> FromIterator<T>
+    for PriorityQueue<T, A, C>
+{
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut this = Self {
+            vec: Vector::from_iter(iter),
+            _compare: C::default(),
+        };
+        this.heapify();
+        this
+    }
+}
+
+unsafe impl<T: Send, A: Allocator + Send, C: Compare<T> + Send> Send for PriorityQueue<T, A, C> {}
+unsafe impl<T: Sync, A: Allocator + Sync, C: Compare<T> + Sync> Sync for PriorityQueue<T, A, C> {}
+
+#[cfg(test)]
+mod test {
+    use crate::compare::Greater;
+    use crate::priority_queue::DefaultPriorityQueue;
+
+    /// A shuffled permutation of `0..100`
+    fn shuffled() -> impl Iterator<Item = u32> {
+        (0..100).map(|n| (n * 37) % 100)
+    }
+
+    #[test]
+    fn layout() {
+        assert_eq!(
+            std::mem::size_of::<DefaultPriorityQueue<u32>>(),
+            std::mem::size_of::<usize>() * 5
+        );
+    }
+
+    #[test]
+    fn push_pop_less() {
+        let mut q: DefaultPriorityQueue<u32> = DefaultPriorityQueue::new();
+        assert!(q.is_empty());
+        assert_eq!(q.pop(), None);
+
+        shuffled().for_each(|n| q.push(n));
+        assert_eq!(q.len(), 100);
+        assert_eq!(q.peek(), Some(&99));
+
+        assert!(std::iter::from_fn(|| q.pop()).eq((0..100).rev()));
+        assert!(q.is_empty());
+    }
+
+    #[test]
+    fn push_pop_greater() {
+        let mut q: DefaultPriorityQueue<u32, Greater<u32>> = DefaultPriorityQueue::new();
+
+        shuffled().for_each(|n| q.push(n));
+        assert_eq!(q.peek(), Some(&0));
+
+        assert!(std::iter::from_fn(|| q.pop()).eq(0..100));
+    }
+
+    #[test]
+    fn from_iter() {
+        let mut q: DefaultPriorityQueue<u32> = shuffled().collect();
+        assert_eq!(q.len(), 100);
+        assert!(std::iter::from_fn(|| q.pop()).eq((0..100).rev()));
+
+        let mut q: DefaultPriorityQueue<u32, Greater<u32>> = shuffled().collect();
+        assert!(std::iter::from_fn(|| q.pop()).eq(0..100));
+    }
+}
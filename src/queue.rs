@@ -25,6 +25,13 @@ impl<'a, T: 'a, A: Allocator + Default> Queue<'a, T, A> {
 }
 
 impl<'a, T: 'a, A: Allocator> Queue<'a, T, A> {
+    /// Returns an iterator which pops elements from the queue in FIFO order.
+    /// Any elements which aren't consumed are dropped along with the
+    /// iterator, so the queue is always left empty
+    pub fn drain(&mut self) -> Drain<'_, 'a, T, A> {
+        Drain { queue: self }
+    }
+
     /// Turns the `Queue` into its inner `Deque`
     pub fn into_inner(self) -> Deque<'a, T, A> {
         self.deque
@@ -81,6 +88,32 @@ impl<'a, T: 'a, A: Allocator> Queue<'a, T, A> {
     }
 }
 
+/// An iterator which pops the elements of a queue in FIFO order
+pub struct Drain<'q, 'a: 'q, T: 'a, A: Allocator> {
+    queue: &'q mut Queue<'a, T, A>,
+}
+
+impl<'q, 'a: 'q, T: 'a, A: Allocator> Iterator for Drain<'q, 'a, T, A> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.queue.pop()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.queue.len();
+        (len, Some(len))
+    }
+}
+
+impl<'q, 'a: 'q, T: 'a, A: Allocator> ExactSizeIterator for Drain<'q, 'a, T, A> {}
+
+impl<'q, 'a: 'q, T: 'a, A: Allocator> Drop for Drain<'q, 'a, T, A> {
+    fn drop(&mut self) {
+        self.for_each(drop);
+    }
+}
+
 impl<'a, T: 'a + Debug, A: Allocator> Debug for Queue<'a, T, A> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         self.deque.fmt(f)
@@ -104,6 +137,9 @@ impl<'a, T: 'a, A: Allocator + Default> FromIterator<T> for Queue<'a, T, A> {
 #[cfg(test)]
 mod test {
     use crate::queue::DefaultQueue;
+    use crate::util::DropCounter;
+    use std::cell::Cell;
+    use std::rc::Rc;
 
     #[test]
     fn layout() {
@@ -147,4 +183,30 @@ mod test {
 
         v.iter().zip(0..256).for_each(|(l, r)| assert_eq!(*l, r));
     }
+
+    #[test]
+    fn drain() {
+        let mut q: DefaultQueue<u32> = (0..5).collect();
+        let drain = q.drain();
+        assert_eq!(drain.len(), 5);
+        assert_eq!(drain.collect::<Vec<_>>(), vec![0, 1, 2, 3, 4]);
+        assert!(q.is_empty());
+
+        // the queue is still usable afterwards
+        q.push(5);
+        assert_eq!(q.pop(), Some(5));
+    }
+
+    #[test]
+    fn drain_partial() {
+        let drops = Rc::new(Cell::new(0));
+        let mut q: DefaultQueue<_> = (0..5).map(|_| DropCounter(drops.clone())).collect();
+        let mut drain = q.drain();
+        std::mem::drop(drain.next());
+        assert_eq!(drops.get(), 1);
+
+        std::mem::drop(drain);
+        assert_eq!(drops.get(), 5);
+        assert!(q.is_empty());
+    }
 }
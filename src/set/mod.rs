@@ -1,14 +1,19 @@
 use crate::{
-    allocator::Allocator,
+    allocator::{Allocator, DefaultAllocator},
     compare::{Compare, Less},
     internal::rb_tree::RBTree,
 };
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+
+/// Set with the default allocator.
+pub type DefaultSet<K, C = Less<K>> = Set<K, DefaultAllocator, C>;
 
 /// A set backed by a red-black tree that is always ordered.
 /// Insertion, lookup, and removal are O(nlgn). If you do not
 /// need ordering, look at `HashSet`, which takes O(1) time
 /// for those operations
-#[derive(Default)]
+#[derive(Clone, Default)]
 pub struct Set<K: PartialEq, A: Allocator, C: Compare<K> = Less<K>> {
     inner: RBTree<K, (), A, C>,
 }
@@ -39,6 +44,20 @@ impl<K: PartialEq, A: Allocator + Default, C: Compare<K>> Set<K, A, C> {
     }
 }
 
+impl<K: PartialEq, A: Allocator + Default, C: Compare<K> + Default> Set<K, A, C> {
+    /// Constructs a balanced set from keys in linear time. The keys must be
+    /// strictly increasing according to the comparator
+    ///
+    /// # Arguments
+    ///
+    /// `iter`: The sorted keys
+    pub fn from_sorted_iter<I: IntoIterator<Item = K>>(iter: I) -> Self {
+        Self {
+            inner: RBTree::from_sorted_iter(iter.into_iter().map(|key| (key, ()))),
+        }
+    }
+}
+
 impl<K: PartialEq, A: Allocator, C: Compare<K>> Set<K, A, C> {
     /// Constructs a set using a specified allocator
     /// and comparator
@@ -64,32 +83,92 @@ impl<K: PartialEq, A: Allocator, C: Compare<K>> Set<K, A, C> {
     /// # Arguments
     ///
     /// `key`: The key to index
-    pub fn contains_key(&self, key: &K) -> bool {
+    pub fn contains_key<Q: ?Sized>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        C: Compare<Q>,
+    {
         self.inner.contains_key(key)
     }
 
-    /// Inserts a key into the set. Returns true on success
+    /// Inserts a key into the set. Returns true if the key wasn't
+    /// already present
     ///
     /// # Arguments
     ///
     /// `key`: The key to insert and index by
-    fn _insert(&mut self, key: K) -> bool {
-        self.inner._insert(key, ()).is_some()
+    pub fn insert(&mut self, key: K) -> bool {
+        self.inner.insert(key, ()).is_none()
     }
 
-    /// Returns an iterator over the elements in the tree.
-    ///
-    /// # Safety
-    /// This iterator is not tested as trees are only partially implemented.
-    pub unsafe fn iter(&self) -> impl Iterator<Item = &K> {
+    /// Returns an iterator over the elements in the set, in order
+    pub fn iter(&self) -> impl Iterator<Item = &K> {
         self.inner.iter().map(|(k, _)| k)
     }
 
+    /// Returns true if the set has no keys in common with `other`. Both sets
+    /// are walked in order at once, so this runs in linear time
+    ///
+    /// # Arguments
+    ///
+    /// `other`: The set to compare against
+    pub fn is_disjoint(&self, other: &Self) -> bool {
+        let mut keys = self.iter().peekable();
+        let mut other_keys = other.iter().peekable();
+        while let (Some(key), Some(other_key)) = (keys.peek(), other_keys.peek()) {
+            match C::cmp(key, other_key) {
+                Ordering::Less => {
+                    keys.next();
+                }
+                Ordering::Equal => return false,
+                Ordering::Greater => {
+                    other_keys.next();
+                }
+            }
+        }
+        true
+    }
+
     /// Returns true if the set contains no elements
     pub fn is_empty(&self) -> bool {
         self.inner.is_empty()
     }
 
+    /// Returns true if every key in the set is also in `other`. Both sets are
+    /// walked in order at once, so this runs in linear time
+    ///
+    /// # Arguments
+    ///
+    /// `other`: The set to compare against
+    pub fn is_subset(&self, other: &Self) -> bool {
+        if self.len() > other.len() {
+            return false;
+        }
+
+        let mut other_keys = other.iter();
+        self.iter().all(|key| {
+            // skip past the other set's keys which are lesser than this one
+            other_keys
+                .by_ref()
+                .find_map(|other_key| match C::cmp(key, other_key) {
+                    Ordering::Less => Some(false),
+                    Ordering::Equal => Some(true),
+                    Ordering::Greater => None,
+                })
+                == Some(true)
+        })
+    }
+
+    /// Returns true if every key in `other` is also in the set. Both sets are
+    /// walked in order at once, so this runs in linear time
+    ///
+    /// # Arguments
+    ///
+    /// `other`: The set to compare against
+    pub fn is_superset(&self, other: &Self) -> bool {
+        other.is_subset(self)
+    }
+
     /// Returns the number of elements in the set
     pub fn len(&self) -> usize {
         self.inner.len()
@@ -98,7 +177,105 @@ impl<K: PartialEq, A: Allocator, C: Compare<K>> Set<K, A, C> {
     /// Removes a key from the set,
     /// returning the element if it was found
     ///
-    fn _remove(&mut self, key: &K) -> Option<K> {
+    /// # Arguments
+    ///
+    /// `key`: The key to remove
+    pub fn remove<Q: ?Sized>(&mut self, key: &Q) -> Option<K>
+    where
+        K: Borrow<Q>,
+        C: Compare<Q>,
+    {
         self.inner.remove_entry(key).map(|(k, _)| k)
     }
+
+    /// Checks that the set's underlying red-black tree is balanced and ordered
+    #[cfg(any(test, debug_assertions))]
+    pub fn validate(&self) -> Result<(), &'static str> {
+        self.inner.validate()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::set::DefaultSet;
+    use std::collections::BTreeSet;
+
+    #[test]
+    fn insert() {
+        let mut set: DefaultSet<_> = DefaultSet::default();
+        assert!(set.insert(2));
+        assert!(set.insert(1));
+        assert!(!set.insert(2));
+
+        assert_eq!(set.len(), 2);
+        assert!(set.contains_key(&1));
+        assert!(!set.contains_key(&3));
+        assert!(set.iter().copied().eq([1, 2]));
+    }
+
+    #[test]
+    fn from_sorted_iter() {
+        let set: DefaultSet<_> = DefaultSet::from_sorted_iter(0..50);
+
+        assert_eq!(set.len(), 50);
+        assert!(set.contains_key(&49));
+        assert!(set.iter().copied().eq(0..50));
+    }
+
+    #[test]
+    fn remove() {
+        let mut set: DefaultSet<_> = DefaultSet::from_sorted_iter(0..20);
+        assert_eq!(set.remove(&5), Some(5));
+        assert_eq!(set.remove(&5), None);
+
+        assert_eq!(set.len(), 19);
+        assert_eq!(set.validate(), Ok(()));
+        assert!(!set.contains_key(&5));
+    }
+
+    #[test]
+    fn set_relations() {
+        let sets: Vec<Vec<u32>> = vec![
+            vec![],
+            vec![1, 2],
+            vec![1, 2, 3],
+            vec![2, 4],
+            vec![5, 6, 7, 8],
+            (0..20).step_by(2).collect(),
+            (0..20).step_by(4).collect(),
+            (0..20).step_by(3).collect(),
+        ];
+        for a in &sets {
+            for b in &sets {
+                let (set_a, set_b) = (
+                    DefaultSet::<u32>::from_sorted_iter(a.iter().copied()),
+                    DefaultSet::<u32>::from_sorted_iter(b.iter().copied()),
+                );
+                let (ref_a, ref_b) = (
+                    a.iter().collect::<BTreeSet<_>>(),
+                    b.iter().collect::<BTreeSet<_>>(),
+                );
+                assert_eq!(set_a.is_subset(&set_b), ref_a.is_subset(&ref_b));
+                assert_eq!(set_a.is_superset(&set_b), ref_a.is_superset(&ref_b));
+                assert_eq!(set_a.is_disjoint(&set_b), ref_a.is_disjoint(&ref_b));
+            }
+        }
+    }
+
+    #[test]
+    fn clone() {
+        let mut set: DefaultSet<u32> = DefaultSet::default();
+        for key in (0..100).map(|n| (n * 37) % 100) {
+            set.insert(key);
+        }
+
+        let mut cloned = set.clone();
+        assert!(cloned.iter().eq(set.iter()));
+
+        cloned.remove(&50);
+        cloned.insert(100);
+        assert!(set.contains_key(&50));
+        assert!(!set.contains_key(&100));
+        assert!(cloned.iter().copied().eq((0..=100).filter(|n| *n != 50)));
+    }
 }
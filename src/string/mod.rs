@@ -1,17 +1,23 @@
 mod null_terminator_allocator;
 
+use std::borrow::Borrow;
+use std::cmp::Ordering;
 use std::convert::Infallible;
+use std::ffi::{c_char, CStr, FromBytesWithNulError};
 use std::str::FromStr;
 use std::{
     fmt::{Debug, Display},
-    ops::{Deref, DerefMut},
+    marker::PhantomData,
+    mem::ManuallyDrop,
+    ops::{Add, AddAssign, Bound, Deref, DerefMut, RangeBounds},
+    ptr,
 };
 
 use crate::allocator::DefaultAllocator;
 use crate::string::null_terminator_allocator::NullTerminatorAllocator;
 use crate::{
     allocator::Allocator,
-    hash::{DefaultHash, Hash},
+    hash::{BorrowHash, DefaultHash, Fnv1a, Hash},
     vector::Vector,
 };
 
@@ -41,6 +47,23 @@ impl<A: Allocator + Default> String<A> {
             vec: Vector::with_capacity(capacity),
         }
     }
+
+    /// Takes the contents out of the string, leaving it empty. The buffer is
+    /// moved rather than copied
+    pub fn take(&mut self) -> Self {
+        self.replace_contents(Self::new())
+    }
+}
+
+impl String<DefaultAllocator> {
+    /// Creates a string by copying a standard string slice
+    ///
+    /// # Arguments
+    ///
+    /// `s`: The string slice
+    pub fn from_std(s: &str) -> Self {
+        Self::from(s)
+    }
 }
 
 impl<A: Allocator> String<A> {
@@ -79,17 +102,80 @@ impl<A: Allocator> String<A> {
         }
     }
 
-    /// Assigns a string to a slice
-    pub fn assign<S: AsRef<str>>(&mut self, buf: S) {
-        self.reserve(buf.as_ref().len());
+    /// Creates a string from the raw parts of an existing heap buffer, such
+    /// as one belonging to an `eastl::string`
+    ///
+    /// # Arguments
+    ///
+    /// `ptr`: The start of the buffer
+    ///
+    /// `len`: The length of the string, excluding the null terminator
+    ///
+    /// `capacity`: The capacity of the string, excluding the null terminator
+    ///
+    /// `allocator`: The allocator which allocated the buffer
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must have been allocated by `allocator` with room for `capacity` bytes
+    /// plus a null terminator, and the first `len` bytes must be valid UTF-8. A null
+    /// `ptr` with a zero capacity is the empty string, which never allocated
+    pub unsafe fn from_raw_parts(ptr: *mut u8, len: usize, capacity: usize, allocator: A) -> Self {
+        assert!(len <= capacity, "length exceeds capacity");
+        if ptr.is_null() {
+            assert_eq!(capacity, 0, "null buffer with a non-zero capacity");
+            return Self::new_in(allocator);
+        }
+        assert_eq!(*ptr.add(len), 0, "string is not null-terminated");
+        Self {
+            vec: Vector {
+                begin_ptr: ptr,
+                end_ptr: ptr.add(len),
+                capacity_ptr: ptr.add(capacity),
+                allocator: NullTerminatorAllocator(allocator),
+                _holds_data: PhantomData,
+            },
+        }
+    }
+
+    /// Decomposes the string into its raw parts: the buffer, the length,
+    /// the capacity, and the allocator. The buffer is not freed, and is null
+    /// if the string never allocated
+    pub fn into_raw_parts(self) -> (*mut u8, usize, usize, A) {
+        let (len, capacity) = (self.len(), self.capacity());
+        let this = ManuallyDrop::new(self);
+        // safety: the string is never dropped, so the allocator is only read once
+        let allocator = unsafe { ptr::read(&this.vec.allocator.0) };
+        (this.vec.begin_ptr, len, capacity, allocator)
+    }
 
+    /// Copies the contents of the string into a standard `String`
+    pub fn into_std_string(self) -> std::string::String {
+        self.as_str().to_owned()
+    }
+
+    /// Assigns a string to a slice, re-using the existing capacity if possible
+    pub fn assign<S: AsRef<str>>(&mut self, buf: S) {
         // copy over and null terminate
-        self.vec.append(buf.as_ref().as_bytes());
+        self.vec.assign(buf.as_ref().as_bytes());
 
         // make sure the end is null-terminated
         unsafe { self.null_terminate() }
     }
 
+    /// Clones the string into a string backed by a different allocator
+    ///
+    /// # Arguments
+    ///
+    /// `allocator`: The allocator used to allocate and de-allocate elements
+    ///
+    /// # Safety
+    ///
+    /// The allocator specified must safely allocate ande de-allocate valid memory
+    pub unsafe fn clone_in<A2: Allocator>(&self, allocator: A2) -> String<A2> {
+        String::from_in(self.as_str(), allocator)
+    }
+
     /// Returns the string as bytes
     pub fn as_bytes(&self) -> &[u8] {
         self.as_ref()
@@ -100,11 +186,114 @@ impl<A: Allocator> String<A> {
         self
     }
 
+    /// Returns a pointer to the string's null-terminated buffer, suitable for
+    /// passing to C APIs. The pointer is valid until the string is next modified
+    pub fn as_c_str_ptr(&self) -> *const c_char {
+        self.as_bytes_with_nul().as_ptr().cast()
+    }
+
+    /// Returns the string as a C string, including its null terminator. Fails
+    /// if the string contains an interior nul byte
+    pub fn as_cstr(&self) -> Result<&CStr, FromBytesWithNulError> {
+        CStr::from_bytes_with_nul(self.as_bytes_with_nul())
+    }
+
+    /// Returns the string as bytes, including the null terminator
+    fn as_bytes_with_nul(&self) -> &[u8] {
+        if self.vec.begin_ptr.is_null() {
+            // we haven't allocated yet, so there's no terminator to point to
+            b"\0"
+        } else {
+            // the allocator always leaves room for the null terminator
+            unsafe { std::slice::from_raw_parts(self.vec.begin_ptr, self.len() + 1) }
+        }
+    }
+
+    /// Returns an iterator over the bytes of the string, excluding the null
+    /// terminator
+    pub fn bytes(&self) -> std::str::Bytes<'_> {
+        self.as_str().bytes()
+    }
+
     /// Returns the capacity of the string
     pub fn capacity(&self) -> usize {
         self.vec.capacity()
     }
 
+    /// Returns the number of chars in the string, as opposed to `len`,
+    /// which counts bytes
+    pub fn char_len(&self) -> usize {
+        self.chars().count()
+    }
+
+    /// Returns an iterator over the chars of the string
+    pub fn chars(&self) -> std::str::Chars<'_> {
+        self.as_str().chars()
+    }
+
+    /// Compares the string to `other`, treating ASCII letters of either case
+    /// as equal
+    ///
+    /// # Arguments
+    ///
+    /// `other`: The string to compare against
+    pub fn cmp_ignore_ascii_case(&self, other: &str) -> Ordering {
+        self.bytes()
+            .map(|b| b.to_ascii_lowercase())
+            .cmp(other.bytes().map(|b| b.to_ascii_lowercase()))
+    }
+
+    /// Removes consecutive repeated chars from the string
+    pub fn dedup(&mut self) {
+        if self.is_empty() {
+            return;
+        }
+
+        let bytes = self.vec.as_slice_mut();
+        let mut previous = None;
+        let mut write = 0;
+        let mut read = 0;
+        while read < bytes.len() {
+            // safety: only bytes before `write <= read` are overwritten, so
+            // everything from `read` onwards is still the original UTF-8
+            let elem = unsafe { std::str::from_utf8_unchecked(&bytes[read..]) }
+                .chars()
+                .next()
+                .unwrap();
+            let elem_len = elem.len_utf8();
+            if previous != Some(elem) {
+                bytes.copy_within(read..read + elem_len, write);
+                write += elem_len;
+                previous = Some(elem);
+            }
+            read += elem_len;
+        }
+
+        unsafe {
+            self.vec.end_ptr = self.vec.begin_ptr.add(write);
+            self.null_terminate()
+        }
+    }
+
+    /// Returns true if the string ends with `pat`
+    ///
+    /// # Arguments
+    ///
+    /// `pat`: The suffix to check for
+    pub fn ends_with(&self, pat: &str) -> bool {
+        self.as_str().ends_with(pat)
+    }
+
+    /// Returns true if the string is equal to `other`, treating ASCII letters
+    /// of either case as equal
+    ///
+    /// # Arguments
+    ///
+    /// `other`: The string to compare against
+    pub fn eq_ignore_ascii_case(&self, other: &str) -> bool {
+        self.as_str().eq_ignore_ascii_case(other)
+    }
+
     /// Returns true if the string is empty
     pub fn is_empty(&self) -> bool {
         self.vec.is_empty()
@@ -120,7 +309,17 @@ impl<A: Allocator> String<A> {
         self.vec.len()
     }
 
-    /// Pushes a new element into the string
+    /// Returns the `n`-th char in the string, or `None` if the string has
+    /// fewer than `n + 1` chars
+    ///
+    /// # Arguments
+    ///
+    /// `n`: The index of the char, counted in chars rather than bytes
+    pub fn nth_char(&self, n: usize) -> Option<char> {
+        self.chars().nth(n)
+    }
+
+    /// Pushes a new char onto the back of the string, encoded as UTF-8
     ///
     /// # Arguments
     ///
@@ -129,53 +328,189 @@ impl<A: Allocator> String<A> {
         self.insert(self.len(), elem)
     }
 
-    /// Pops an element off of the back of the array
+    /// Pops the last char off of the back of the string, removing all
+    /// of its UTF-8 bytes
     pub fn pop(&mut self) -> Option<char> {
-        let elem = self.vec.pop().map(|elem| elem as char);
+        let elem = self.chars().next_back()?;
 
-        // null terminate
-        unsafe { self.null_terminate() }
+        unsafe {
+            self.vec.end_ptr = self.vec.end_ptr.sub(elem.len_utf8());
+
+            // null terminate
+            self.null_terminate()
+        }
 
-        elem
+        Some(elem)
     }
 
-    /// Inserts a char into the string at an index.
-    /// `index` must be less than or equal to `size`
+    /// Inserts a char into the string at a byte index, encoded as UTF-8.
+    /// `index` must be less than or equal to `size` and lie on a char boundary
     ///
     /// # Arguments
     ///
-    /// `index`: The index to insert the char
+    /// `index`: The byte index to insert the char
     ///
     /// `elem`: The char to add to the string
     pub fn insert(&mut self, index: usize, elem: char) {
-        self.vec.insert(index, elem as u8);
+        self.insert_str(index, elem.encode_utf8(&mut [0; 4]))
+    }
 
-        // null terminate
-        unsafe { self.null_terminate() }
+    /// Inserts a string slice into the string at a byte index.
+    /// `index` must be less than or equal to `len` and lie on a char boundary
+    ///
+    /// # Arguments
+    ///
+    /// `index`: The byte index to insert the slice at
+    ///
+    /// `s`: The string slice to insert
+    pub fn insert_str(&mut self, index: usize, s: &str) {
+        let len = self.len();
+        assert!(index <= len, "index out of bounds");
+        assert!(self.is_char_boundary(index), "index is not a char boundary");
+        if s.is_empty() {
+            return;
+        }
+
+        self.vec.reserve(s.len());
+
+        unsafe {
+            // shift the tail right in one go, then copy the slice in
+            let insertion_ptr = self.vec.begin_ptr.add(index);
+            insertion_ptr.copy_to(insertion_ptr.add(s.len()), len - index);
+            insertion_ptr.copy_from_nonoverlapping(s.as_ptr(), s.len());
+            self.vec.end_ptr = self.vec.end_ptr.add(s.len());
+
+            self.null_terminate()
+        }
+    }
+
+    /// Appends a string slice to the end of the string
+    ///
+    /// # Arguments
+    ///
+    /// `s`: The string slice to append
+    pub fn push_str(&mut self, s: &str) {
+        self.insert_str(self.len(), s)
     }
 
-    /// Remove the char at the index and return it
+    /// Remove the char at a byte index and return it. `index` must lie on
+    /// a char boundary. Returns `None` if `index` is out of bounds
     ///
     /// # Arguments
     ///
-    /// `index`: The index of the element to remove
+    /// `index`: The byte index of the char to remove
     pub fn remove(&mut self, index: usize) -> Option<char> {
-        // account for null terminator
         if index >= self.len() {
-            None
-        } else {
-            self.vec.remove(index).map(|elem| elem as char)
+            return None;
+        }
+        assert!(self.is_char_boundary(index), "index is not a char boundary");
+
+        let elem = self.as_str()[index..].chars().next()?;
+        self.remove_range(index..index + elem.len_utf8());
+        Some(elem)
+    }
+
+    /// Removes a byte range from the string, shifting the tail down. The
+    /// bounds of the range must lie on char boundaries
+    ///
+    /// # Arguments
+    ///
+    /// `range`: The byte range to remove
+    pub fn remove_range<R: RangeBounds<usize>>(&mut self, range: R) {
+        self.replace_range(range, "")
+    }
+
+    /// Replaces the contents of the string with another string, returning
+    /// the old contents. The buffers are swapped rather than copied
+    ///
+    /// # Arguments
+    ///
+    /// `new`: The string holding the new contents
+    pub fn replace_contents(&mut self, mut new: Self) -> Self {
+        std::mem::swap(&mut self.vec, &mut new.vec);
+        new
+    }
+
+    /// Replaces a byte range of the string with a string slice, shifting the
+    /// tail to fit. The bounds of the range must lie on char boundaries
+    ///
+    /// # Arguments
+    ///
+    /// `range`: The byte range to replace
+    ///
+    /// `replacement`: The string slice to put in place of the range
+    pub fn replace_range<R: RangeBounds<usize>>(&mut self, range: R, replacement: &str) {
+        let len = self.len();
+        let start = match range.start_bound() {
+            Bound::Included(&start) => start,
+            Bound::Excluded(&start) => start + 1,
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&end) => end + 1,
+            Bound::Excluded(&end) => end,
+            Bound::Unbounded => len,
+        };
+        assert!(start <= end && end <= len, "range out of bounds");
+        assert!(
+            self.is_char_boundary(start) && self.is_char_boundary(end),
+            "range is not on char boundaries"
+        );
+        if start == end && replacement.is_empty() {
+            return;
+        }
+
+        let new_len = len - (end - start) + replacement.len();
+        self.vec.reserve(new_len.saturating_sub(len));
+
+        unsafe {
+            // shift the tail in one go, then copy the replacement in
+            let start_ptr = self.vec.begin_ptr.add(start);
+            self.vec
+                .begin_ptr
+                .add(end)
+                .copy_to(start_ptr.add(replacement.len()), len - end);
+            start_ptr.copy_from_nonoverlapping(replacement.as_ptr(), replacement.len());
+            self.vec.end_ptr = self.vec.begin_ptr.add(new_len);
+
+            self.null_terminate()
         }
     }
 
-    /// Reserves space for chars within the string
+    /// Reserves space for at least `additional` more bytes within the string.
+    /// The allocator always leaves room for the null terminator on top
+    ///
+    /// # Arguments
+    ///
+    /// `additional`: The number of additional bytes
+    pub fn reserve(&mut self, additional: usize) {
+        self.vec.reserve(additional)
+    }
+
+    /// Reserves space for exactly `additional` more bytes within the string,
+    /// plus the null terminator
     ///
     /// # Arguments
     ///
-    /// `capacity`: The new capacity of the string
-    pub fn reserve(&mut self, capacity: usize) {
-        // make space for the null terminator
-        self.vec.reserve(capacity + 1)
+    /// `additional`: The number of additional bytes
+    pub fn reserve_exact(&mut self, additional: usize) {
+        self.vec.reserve_exact(additional)
+    }
+
+    /// Shrinks the capacity of the string to its length, keeping room for
+    /// the null terminator
+    pub fn shrink_to_fit(&mut self) {
+        self.vec.shrink_to_fit();
+        unsafe { self.null_terminate() }
+    }
+
+    /// Returns true if the string starts with `pat`
+    ///
+    /// # Arguments
+    ///
+    /// `pat`: The prefix to check for
+    pub fn starts_with(&self, pat: &str) -> bool {
+        self.as_str().starts_with(pat)
     }
 
     /// Null terminate the string.
@@ -191,6 +526,61 @@ impl<A: Allocator> String<A> {
     }
 }
 
+impl<A: Allocator + Clone> String<A> {
+    /// Splits the string on `sep`, collecting the pieces into a vector of
+    /// strings which use clones of this string's allocator. Consecutive
+    /// separators produce empty pieces, like `str::split`
+    ///
+    /// # Arguments
+    ///
+    /// `sep`: The separator to split on
+    pub fn split_to_vector(&self, sep: char) -> Vector<String<A>, DefaultAllocator> {
+        self.as_str()
+            .split(sep)
+            .map(|piece| unsafe { String::from_in(piece, self.vec.allocator.0.clone()) })
+            .collect()
+    }
+
+    /// Splits the string into lines, collecting them into a vector of strings
+    /// which use clones of this string's allocator. Lines end with either
+    /// `\n` or `\r\n`, which aren't included, like `str::lines`
+    pub fn lines_to_vector(&self) -> Vector<String<A>, DefaultAllocator> {
+        self.as_str()
+            .lines()
+            .map(|line| unsafe { String::from_in(line, self.vec.allocator.0.clone()) })
+            .collect()
+    }
+}
+
+impl<A: Allocator> Add<&str> for String<A> {
+    type Output = Self;
+
+    fn add(mut self, rhs: &str) -> Self::Output {
+        self.push_str(rhs);
+        self
+    }
+}
+
+impl<A: Allocator> Add<&String<A>> for String<A> {
+    type Output = Self;
+
+    fn add(self, rhs: &String<A>) -> Self::Output {
+        self + rhs.as_str()
+    }
+}
+
+impl<A: Allocator> AddAssign<&str> for String<A> {
+    fn add_assign(&mut self, rhs: &str) {
+        self.push_str(rhs)
+    }
+}
+
+impl<A: Allocator> AddAssign<&String<A>> for String<A> {
+    fn add_assign(&mut self, rhs: &String<A>) {
+        self.push_str(rhs)
+    }
+}
+
 impl<A: Allocator> AsRef<[u8]> for String<A> {
     fn as_ref(&self) -> &[u8] {
         self.vec.as_slice()
@@ -203,12 +593,22 @@ impl<A: Allocator> AsRef<str> for String<A> {
     }
 }
 
+impl<A: Allocator> Borrow<str> for String<A> {
+    fn borrow(&self) -> &str {
+        self
+    }
+}
+
 impl<A: Allocator + Clone> Clone for String<A> {
     fn clone(&self) -> Self {
         Self {
             vec: self.vec.clone(),
         }
     }
+
+    fn clone_from(&mut self, source: &Self) {
+        self.assign(source.as_str())
+    }
 }
 
 impl<A: Allocator> Debug for String<A> {
@@ -251,6 +651,24 @@ impl<A: Allocator> PartialEq for String<A> {
 
 impl<A: Allocator> Eq for String<A> {}
 
+impl<A: Allocator> std::hash::Hash for String<A> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state)
+    }
+}
+
+impl<A: Allocator> PartialOrd for String<A> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<A: Allocator> Ord for String<A> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.as_str().cmp(other.as_str())
+    }
+}
+
 impl<A: Allocator + Default> From<&str> for String<A> {
     fn from(s: &str) -> Self {
         unsafe { Self::from_in(s, A::default()) }
@@ -271,14 +689,28 @@ impl<A: Allocator> Hash<String<A>> for DefaultHash<String<A>> {
     }
 }
 
+impl<A: Allocator> Hash<String<A>> for Fnv1a {
+    fn hash(val: &String<A>) -> usize {
+        Fnv1a::hash(val.as_str())
+    }
+}
+
+impl<A: Allocator> BorrowHash<str> for DefaultHash<String<A>> {
+    fn hash_borrowed(val: &str) -> usize {
+        DefaultHash::<str>::hash(val)
+    }
+}
+
 unsafe impl<A: Allocator + Send> Send for String<A> {}
 unsafe impl<A: Allocator + Sync> Sync for String<A> {}
 
 #[cfg(test)]
 mod test {
     use memoffset::offset_of;
+    use std::cmp::Ordering;
+    use std::ffi::CStr;
 
-    use crate::allocator::DefaultAllocator;
+    use crate::allocator::{CountingAllocator, DefaultAllocator};
     use crate::string::DefaultString;
 
     use super::String;
@@ -295,6 +727,68 @@ mod test {
         assert!(s.is_empty());
     }
 
+    #[test]
+    fn bytes_chars() {
+        let s = DefaultString::from("aé€");
+        assert_eq!(s.bytes().count(), 6);
+        assert_eq!(
+            s.bytes().map(u32::from).sum::<u32>(),
+            0x61 + 0xc3 + 0xa9 + 0xe2 + 0x82 + 0xac
+        );
+        assert_eq!(s.chars().collect::<Vec<_>>(), vec!['a', 'é', '€']);
+        assert_eq!(s.chars().next_back(), Some('€'));
+    }
+
+    #[test]
+    fn split_to_vector() {
+        let s = DefaultString::from("a,b,,c");
+        let pieces = s.split_to_vector(',');
+        assert_eq!(pieces.len(), 4);
+        assert!(pieces
+            .iter()
+            .map(|piece| piece.as_str())
+            .eq(["a", "b", "", "c"]));
+
+        assert_eq!(DefaultString::new().split_to_vector(',').len(), 1);
+    }
+
+    #[test]
+    fn lines_to_vector() {
+        let s = DefaultString::from("first\nsecond\r\n\nlast");
+        let lines = s.lines_to_vector();
+        assert!(lines
+            .iter()
+            .map(|line| line.as_str())
+            .eq(["first", "second", "", "last"]));
+    }
+
+    #[test]
+    fn ignore_ascii_case() {
+        let s = DefaultString::from("Hello");
+        assert!(s.eq_ignore_ascii_case("HELLO"));
+        assert!(!s.eq_ignore_ascii_case("hellp"));
+        assert_eq!(s.cmp_ignore_ascii_case("HELLO"), Ordering::Equal);
+        assert_eq!(s.cmp_ignore_ascii_case("hellp"), Ordering::Less);
+        assert_eq!(s.cmp_ignore_ascii_case("HELL"), Ordering::Greater);
+    }
+
+    #[test]
+    fn char_len_nth_char() {
+        let s = DefaultString::from("abc");
+        assert_eq!(s.char_len(), 3);
+        assert_eq!(s.char_len(), s.len());
+        assert_eq!(s.nth_char(1), Some('b'));
+        assert_eq!(s.nth_char(3), None);
+
+        let s = DefaultString::from("aé€😀");
+        assert_eq!(s.len(), 10);
+        assert_eq!(s.char_len(), 4);
+        assert_eq!(s.nth_char(0), Some('a'));
+        assert_eq!(s.nth_char(2), Some('€'));
+        assert_eq!(s.nth_char(3), Some('😀'));
+        assert_eq!(s.nth_char(4), None);
+    }
+
     #[test]
     fn is_full() {
         let mut s = DefaultString::new();
@@ -365,6 +859,24 @@ mod test {
         s.insert(3, 'c');
     }
 
+    #[test]
+    fn insert_str() {
+        let mut s = DefaultString::from("abc");
+        s.insert_str(1, "XY");
+        assert_eq!(s.as_str(), "aXYbc");
+        assert_eq!(unsafe { *s.vec.end_ptr }, 0);
+
+        s.push_str("de");
+        assert_eq!(s.as_str(), "aXYbcde");
+    }
+
+    #[test]
+    #[should_panic(expected = "index is not a char boundary")]
+    fn insert_str_not_char_boundary() {
+        let mut s = DefaultString::from("aé");
+        s.insert_str(2, "XY");
+    }
+
     #[test]
     fn remove() {
         let mut s = DefaultString::from("ab");
@@ -373,6 +885,23 @@ mod test {
         assert_eq!(s.as_str(), "a");
     }
 
+    #[test]
+    fn remove_multibyte() {
+        let mut s = DefaultString::from("aé😀b");
+        assert_eq!(s.remove(1), Some('é'));
+        assert_eq!(s.as_str(), "a😀b");
+        assert_eq!(s.remove(1), Some('😀'));
+        assert_eq!(s.as_str(), "ab");
+        assert_eq!(unsafe { *s.vec.end_ptr }, 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "index is not a char boundary")]
+    fn remove_not_char_boundary() {
+        let mut s = DefaultString::from("aé");
+        s.remove(2);
+    }
+
     #[test]
     fn null_terminated() {
         let mut s = DefaultString::from("a");
@@ -391,6 +920,53 @@ mod test {
         assert_eq!(unsafe { *s.vec.end_ptr }, 0);
     }
 
+    #[test]
+    fn assign() {
+        let mut s = DefaultString::from("abcd");
+        let capacity = s.capacity();
+        s.assign("ef");
+        assert_eq!(s.as_str(), "ef");
+        assert_eq!(s.capacity(), capacity);
+    }
+
+    #[test]
+    fn clone_in() {
+        let s = DefaultString::from("abc");
+        let allocator = CountingAllocator::default();
+
+        let cloned = unsafe { s.clone_in(allocator.clone()) };
+        assert_eq!(cloned.as_str(), "abc");
+        // account for the null terminator
+        assert_eq!(allocator.live_bytes(), 4);
+
+        drop(cloned);
+        assert_eq!(allocator.live_bytes(), 0);
+    }
+
+    #[test]
+    fn raw_parts() {
+        let s = DefaultString::from("abc");
+        let (ptr, len, capacity, allocator) = s.into_raw_parts();
+        assert_eq!(len, 3);
+        assert_eq!(unsafe { *ptr.add(len) }, 0);
+
+        let s = unsafe { DefaultString::from_raw_parts(ptr, len, capacity, allocator) };
+        assert_eq!(s.as_str(), "abc");
+        assert_eq!(s.capacity(), capacity);
+    }
+
+    #[test]
+    fn raw_parts_empty() {
+        let (ptr, len, capacity, allocator) = DefaultString::new().into_raw_parts();
+        assert!(ptr.is_null());
+        assert_eq!((len, capacity), (0, 0));
+
+        let mut s = unsafe { DefaultString::from_raw_parts(ptr, len, capacity, allocator) };
+        assert!(s.is_empty());
+        s.push_str("abc");
+        assert_eq!(s.as_str(), "abc");
+    }
+
     #[test]
     fn equals() {
         let s1 = DefaultString::from("abcd");
@@ -400,4 +976,225 @@ mod test {
         assert!(s1.eq(&s2));
         assert!(s1.ne(&s3));
     }
+
+    #[test]
+    fn c_str() {
+        let s = DefaultString::from("hello");
+        let c_str = unsafe { CStr::from_ptr(s.as_c_str_ptr()) };
+        assert_eq!(c_str.to_str(), Ok("hello"));
+        assert_eq!(s.as_cstr().unwrap(), c_str);
+
+        let empty = DefaultString::new();
+        assert_eq!(
+            unsafe { CStr::from_ptr(empty.as_c_str_ptr()) }.to_bytes(),
+            b""
+        );
+        assert_eq!(empty.as_cstr().unwrap().to_bytes(), b"");
+
+        let interior_nul = DefaultString::from("a\0b");
+        assert!(interior_nul.as_cstr().is_err());
+    }
+
+    #[test]
+    fn std_hash() {
+        let mut set = std::collections::HashSet::new();
+        set.insert(DefaultString::from("abc"));
+
+        assert!(set.contains(&DefaultString::from("abc")));
+        // `Borrow<str>` hashes the same as the string
+        assert!(set.contains("abc"));
+        assert!(!set.contains("abd"));
+    }
+
+    #[test]
+    fn add() {
+        let s = DefaultString::from("a") + "b" + "c";
+        assert_eq!(s.as_str(), "abc");
+
+        let s = s + &DefaultString::from("de");
+        assert_eq!(s.as_str(), "abcde");
+        assert_eq!(unsafe { *s.vec.end_ptr }, 0);
+    }
+
+    #[test]
+    fn add_assign() {
+        let mut s = DefaultString::new();
+        s += "ab";
+        s += &DefaultString::from("cd");
+        assert_eq!(s.as_str(), "abcd");
+        assert_eq!(unsafe { *s.vec.end_ptr }, 0);
+    }
+
+    #[test]
+    fn dedup() {
+        let mut s = DefaultString::from("aaabbbccd");
+        s.dedup();
+        assert_eq!(s.as_str(), "abcd");
+        assert_eq!(unsafe { *s.vec.end_ptr }, 0);
+
+        let mut s = DefaultString::from("ééé  ü€€ü");
+        s.dedup();
+        assert_eq!(s.as_str(), "é ü€ü");
+        assert_eq!(unsafe { *s.vec.end_ptr }, 0);
+
+        let mut s = DefaultString::new();
+        s.dedup();
+        assert!(s.is_empty());
+    }
+
+    #[test]
+    fn std_string() {
+        let s = DefaultString::from_std("héllo");
+        assert_eq!(s.as_str(), "héllo");
+        assert_eq!(unsafe { *s.vec.end_ptr }, 0);
+
+        let std_string = s.into_std_string();
+        assert_eq!(std_string, "héllo");
+        assert_eq!(DefaultString::from_std(&std_string).as_str(), std_string);
+
+        assert!(DefaultString::new().into_std_string().is_empty());
+    }
+
+    #[test]
+    fn reserve_exact_shrink_to_fit() {
+        let mut s = DefaultString::new();
+        s.reserve_exact(10);
+        assert_eq!(s.capacity(), 10);
+
+        s.push_str("abc");
+        s.reserve(2);
+        assert_eq!(s.capacity(), 10);
+        s.reserve_exact(20);
+        assert_eq!(s.capacity(), 23);
+
+        s.shrink_to_fit();
+        assert_eq!(s.capacity(), s.len());
+        assert_eq!(s.as_cstr().unwrap().to_bytes(), b"abc");
+
+        s.assign("");
+        s.shrink_to_fit();
+        assert_eq!(s.capacity(), 0);
+        assert_eq!(s.as_cstr().unwrap().to_bytes(), b"");
+    }
+
+    #[test]
+    fn clone_from() {
+        let source = unsafe { String::from_in("abc", CountingAllocator::default()) };
+
+        let allocator = CountingAllocator::default();
+        let mut s = unsafe { String::from_in("longer text", allocator.clone()) };
+        assert_eq!(allocator.allocations(), 1);
+
+        s.clone_from(&source);
+        assert_eq!(allocator.allocations(), 1);
+        assert_eq!(s.as_str(), "abc");
+        assert_eq!(s.as_cstr().unwrap().to_bytes(), b"abc");
+    }
+
+    #[test]
+    fn push_pop_multibyte() {
+        let mut s = DefaultString::from("a");
+        s.push('€');
+        assert_eq!(s.len(), 4);
+        assert_eq!(s.as_str(), "a€");
+
+        assert_eq!(s.pop(), Some('€'));
+        assert_eq!(s.len(), 1);
+        assert_eq!(unsafe { *s.vec.end_ptr }, 0);
+        assert_eq!(s.pop(), Some('a'));
+        assert_eq!(s.pop(), None);
+    }
+
+    #[test]
+    fn insert_multibyte() {
+        let mut s = DefaultString::from("ac");
+        s.insert(1, 'ß');
+        s.insert(0, '🦀');
+        assert_eq!(s.as_str(), "🦀aßc");
+        assert_eq!(s.pop(), Some('c'));
+        assert_eq!(s.pop(), Some('ß'));
+        assert_eq!(s.as_str(), "🦀a");
+    }
+
+    #[test]
+    fn take() {
+        let mut s = DefaultString::from("hello");
+        let ptr = s.as_ptr();
+
+        let taken = s.take();
+        assert_eq!(taken.as_str(), "hello");
+        assert_eq!(taken.as_ptr(), ptr);
+        assert!(s.is_empty());
+        assert_eq!(s.capacity(), 0);
+
+        s.push_str("again");
+        assert_eq!(s.as_str(), "again");
+    }
+
+    #[test]
+    fn replace_contents() {
+        let mut s = DefaultString::from("old");
+        let new = DefaultString::from("new");
+        let ptr = new.as_ptr();
+
+        let old = s.replace_contents(new);
+        assert_eq!(old.as_str(), "old");
+        assert_eq!(s.as_str(), "new");
+        assert_eq!(s.as_ptr(), ptr);
+    }
+
+    #[test]
+    fn starts_ends_with() {
+        let s = DefaultString::from("key=value");
+        assert!(s.starts_with("key"));
+        assert!(s.starts_with("key=value"));
+        assert!(!s.starts_with("value"));
+        assert!(!s.starts_with("key=value!"));
+
+        assert!(s.ends_with("value"));
+        assert!(!s.ends_with("key"));
+
+        // every string starts and ends with the empty string
+        assert!(s.starts_with(""));
+        assert!(s.ends_with(""));
+        assert!(DefaultString::new().starts_with(""));
+        assert!(!DefaultString::new().ends_with("a"));
+    }
+
+    #[test]
+    fn remove_range() {
+        let mut s = DefaultString::from("hello, world");
+        s.remove_range(5..7);
+        assert_eq!(s.as_str(), "helloworld");
+        s.remove_range(..=4);
+        assert_eq!(s.as_str(), "world");
+        s.remove_range(2..);
+        assert_eq!(s.as_str(), "wo");
+        assert_eq!(unsafe { *s.as_c_str_ptr().add(2) }, 0);
+        s.remove_range(..);
+        assert!(s.is_empty());
+    }
+
+    #[test]
+    fn replace_range() {
+        let mut s = DefaultString::from("hello, world");
+        s.replace_range(7.., "everyone out there");
+        assert_eq!(s.as_str(), "hello, everyone out there");
+        s.replace_range(7..15, "you");
+        assert_eq!(s.as_str(), "hello, you out there");
+        s.replace_range(..0, "oh, ");
+        assert_eq!(s.as_str(), "oh, hello, you out there");
+        assert_eq!(unsafe { *s.as_c_str_ptr().add(s.len()) }, 0);
+
+        let mut s = DefaultString::new();
+        s.replace_range(.., "abc");
+        assert_eq!(s.as_str(), "abc");
+    }
+
+    #[test]
+    #[should_panic]
+    fn replace_range_not_char_boundary() {
+        let mut s = DefaultString::from("h\u{e9}llo");
+        s.replace_range(..2, "a");
+    }
 }
@@ -15,6 +15,9 @@ unsafe impl<A: Allocator> Allocator for NullTerminatorAllocator<A> {
         // always leave room for the null terminator
         self.0.deallocate_raw_aligned(p, n + 1, align)
     }
+    fn is_compatible(&self, other: &Self) -> bool {
+        self.0.is_compatible(&other.0)
+    }
 }
 
 impl<A: Allocator + Clone> Clone for NullTerminatorAllocator<A> {
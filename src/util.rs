@@ -1,4 +1,6 @@
 use std::mem;
+#[cfg(test)]
+use std::{cell::Cell, rc::Rc};
 
 /// Rotates the pair of iterators towards `next`.
 pub unsafe fn rotate<'a, I: 'a, I1: Iterator<Item = &'a mut I>, I2: Iterator<Item = &'a mut I>>(
@@ -9,3 +11,15 @@ pub unsafe fn rotate<'a, I: 'a, I1: Iterator<Item = &'a mut I>, I2: Iterator<Ite
         mem::swap(current, next)
     }
 }
+
+/// An element which keeps track of the number of times it was dropped.
+/// Clones of the counter are shared between elements
+#[cfg(test)]
+pub(crate) struct DropCounter(pub(crate) Rc<Cell<usize>>);
+
+#[cfg(test)]
+impl Drop for DropCounter {
+    fn drop(&mut self) {
+        self.0.set(self.0.get() + 1);
+    }
+}
@@ -1,10 +1,12 @@
 use std::{
     fmt::Debug,
     marker::PhantomData,
-    ops::{Deref, DerefMut},
+    mem::{size_of, size_of_val, MaybeUninit},
+    ops::{Bound, Deref, DerefMut, RangeBounds},
 };
 
 use crate::allocator::{Allocator, DefaultAllocator};
+use duplicate::duplicate_item;
 
 /// Vector with the default allocator.
 pub type DefaultVector<V> = Vector<V, DefaultAllocator>;
@@ -37,12 +39,47 @@ impl<T: Sized, A: Allocator + Default> Vector<T, A> {
     /// `capacity`: The initial capacity of the vector
     pub fn with_capacity(capacity: usize) -> Self {
         let mut v = Vector::new();
-        v.reserve(capacity);
+        v.reserve_exact(capacity);
+        v
+    }
+
+    /// Takes the elements out of the vector, leaving it empty without a
+    /// buffer. The buffer is moved rather than copied, like `std::mem::take`
+    pub fn take(&mut self) -> Self {
+        std::mem::replace(self, Self::new())
+    }
+}
+
+impl<T: Sized> Vector<T, DefaultAllocator> {
+    /// Creates a vector by moving the elements out of a standard `Vec`
+    ///
+    /// # Arguments
+    ///
+    /// `vec`: The standard vector
+    pub fn from_std_vec(mut vec: Vec<T>) -> Self {
+        let mut v = Self::with_capacity(vec.len());
+        if !vec.is_empty() {
+            unsafe {
+                // the elements are now owned by the new vector
+                v.begin_ptr
+                    .copy_from_nonoverlapping(vec.as_ptr(), vec.len());
+                v.end_ptr = v.begin_ptr.add(vec.len());
+                vec.set_len(0);
+            }
+        }
         v
     }
 }
 
 impl<T: Sized, A: Allocator> Vector<T, A> {
+    /// The largest capacity whose size in bytes fits in an `isize`
+    const MAX_CAPACITY: usize = isize::MAX as usize
+        / if size_of::<T>() == 0 {
+            1
+        } else {
+            size_of::<T>()
+        };
+
     /// Creates a vector with a custom allocator
     ///
     /// # Arguments
@@ -79,11 +116,66 @@ impl<T: Sized, A: Allocator> Vector<T, A> {
         }
     }
 
+    /// Returns a pointer to the vector's buffer, which may be null if the
+    /// vector has never allocated. The pointer is valid for `len()` reads
+    /// until the vector is next modified
+    pub fn as_ptr(&self) -> *const T {
+        self.begin_ptr
+    }
+
+    /// Returns a mutable pointer to the vector's buffer, which may be null if
+    /// the vector has never allocated. Writes through the pointer must stay
+    /// within `len()` elements, since the length is not updated
+    pub fn as_mut_ptr(&mut self) -> *mut T {
+        self.begin_ptr
+    }
+
+    /// Moves all of the elements of another vector onto the back of the
+    /// vector, leaving the other vector empty. If the vector is empty and the
+    /// allocators are compatible, the other vector's buffer is taken whole
+    ///
+    /// # Arguments
+    ///
+    /// `other`: The vector to move the elements out of
+    pub fn append_vector(&mut self, other: &mut Self) {
+        if self.is_empty() && self.allocator.is_compatible(&other.allocator) {
+            // swap the buffers so our empty buffer is freed with the other vector
+            std::mem::swap(&mut self.begin_ptr, &mut other.begin_ptr);
+            std::mem::swap(&mut self.end_ptr, &mut other.end_ptr);
+            std::mem::swap(&mut self.capacity_ptr, &mut other.capacity_ptr);
+            return;
+        }
+
+        let other_len = other.len();
+        if other_len == 0 {
+            return;
+        }
+        self.reserve(other_len);
+        unsafe {
+            // the elements are moved, so only the other vector's length is reset
+            self.end_ptr
+                .copy_from_nonoverlapping(other.begin_ptr, other_len);
+            self.end_ptr = self.end_ptr.add(other_len);
+            other.end_ptr = other.begin_ptr;
+        }
+    }
+
     /// Returns the capacity of the vector
     pub fn capacity(&self) -> usize {
         (unsafe { self.capacity_ptr.offset_from(self.begin_ptr) }) as usize
     }
 
+    /// Returns an iterator over mutable chunks of exactly `chunk_size`
+    /// elements, for processing the vector in batches. Any elements left over
+    /// are available from the iterator's `into_remainder`
+    ///
+    /// # Arguments
+    ///
+    /// `chunk_size`: The number of elements in each chunk, which must not be zero
+    pub fn chunks_exact_mut(&mut self, chunk_size: usize) -> std::slice::ChunksExactMut<'_, T> {
+        self.as_slice_mut().chunks_exact_mut(chunk_size)
+    }
+
     /// Clears all of the contents
     pub fn clear(&mut self) {
         if !self.begin_ptr.is_null() {
@@ -91,7 +183,8 @@ impl<T: Sized, A: Allocator> Vector<T, A> {
                 // drop all elements in place
                 std::ptr::drop_in_place(self.as_slice_mut());
                 // free the array
-                self.allocator.deallocate::<T>(self.begin_ptr, self.len())
+                self.allocator
+                    .deallocate::<T>(self.begin_ptr, self.capacity())
             }
         }
 
@@ -101,6 +194,37 @@ impl<T: Sized, A: Allocator> Vector<T, A> {
         self.capacity_ptr = std::ptr::null_mut();
     }
 
+    /// Returns a reference to the first element, or `None` if the vector is empty
+    pub fn first(&self) -> Option<&T> {
+        self.as_slice().first()
+    }
+
+    /// Returns a reference to the first `N` elements as an array, or `None` if
+    /// the vector has fewer than `N` elements
+    pub fn first_chunk<const N: usize>(&self) -> Option<&[T; N]> {
+        self.as_slice().first_chunk()
+    }
+
+    /// Returns a mutable reference to the first element, or `None` if the vector is empty
+    pub fn first_mut(&mut self) -> Option<&mut T> {
+        self.as_slice_mut().first_mut()
+    }
+
+    /// Moves the elements of the vector into a standard `Vec`
+    pub fn into_std_vec(mut self) -> Vec<T> {
+        let mut vec: Vec<T> = Vec::with_capacity(self.len());
+        if !self.is_empty() {
+            unsafe {
+                // the elements are now owned by the standard vector
+                vec.as_mut_ptr()
+                    .copy_from_nonoverlapping(self.begin_ptr, self.len());
+                vec.set_len(self.len());
+                self.end_ptr = self.begin_ptr;
+            }
+        }
+        vec
+    }
+
     /// Returns true if the vector is empty
     pub fn is_empty(&self) -> bool {
         self.len() == 0
@@ -111,6 +235,32 @@ impl<T: Sized, A: Allocator> Vector<T, A> {
         self.len() == self.capacity()
     }
 
+    /// Returns an iterator over the elements of the vector
+    pub fn iter(&self) -> std::slice::Iter<'_, T> {
+        self.as_slice().iter()
+    }
+
+    /// Returns a mutable iterator over the elements of the vector
+    pub fn iter_mut(&mut self) -> std::slice::IterMut<'_, T> {
+        self.as_slice_mut().iter_mut()
+    }
+
+    /// Returns a reference to the last element, or `None` if the vector is empty
+    pub fn last(&self) -> Option<&T> {
+        self.as_slice().last()
+    }
+
+    /// Returns a reference to the last `N` elements as an array, or `None` if
+    /// the vector has fewer than `N` elements
+    pub fn last_chunk<const N: usize>(&self) -> Option<&[T; N]> {
+        self.as_slice().last_chunk()
+    }
+
+    /// Returns a mutable reference to the last element, or `None` if the vector is empty
+    pub fn last_mut(&mut self) -> Option<&mut T> {
+        self.as_slice_mut().last_mut()
+    }
+
     /// Returns the length of the vector
     pub fn len(&self) -> usize {
         (unsafe { self.end_ptr.offset_from(self.begin_ptr) }) as usize
@@ -122,14 +272,25 @@ impl<T: Sized, A: Allocator> Vector<T, A> {
     ///
     /// `elem`: The new element
     pub fn push(&mut self, elem: T) {
+        self.push_mut(elem);
+    }
+
+    /// Pushes a new element into the vector, returning a reference to it
+    ///
+    /// # Arguments
+    ///
+    /// `elem`: The new element
+    pub fn push_mut(&mut self, elem: T) -> &mut T {
         // see if we should expand
         if self.is_full() {
             self.grow();
         }
         // add the new element and increment size
         unsafe {
-            self.end_ptr.write(elem);
+            let slot = self.end_ptr;
+            slot.write(elem);
             self.increment_size();
+            &mut *slot
         }
     }
 
@@ -193,32 +354,279 @@ impl<T: Sized, A: Allocator> Vector<T, A> {
         }
     }
 
-    /// Reserves space for elements within the vector
+    /// Retains only the elements that satisfy a predicate, preserving their order
+    ///
+    /// # Arguments
+    ///
+    /// `f`: The predicate, which returns false for elements to remove
+    pub fn retain<F: FnMut(&T) -> bool>(&mut self, mut f: F) {
+        self.retain_mut(|elem| f(elem))
+    }
+
+    /// Retains only the elements that satisfy a predicate, which may mutate
+    /// them, preserving their order
     ///
     /// # Arguments
     ///
-    /// `additional`: The capacity to add to the vector
+    /// `f`: The predicate, which returns false for elements to remove
+    pub fn retain_mut<F: FnMut(&mut T) -> bool>(&mut self, mut f: F) {
+        let len = self.len();
+        // pretend the vector is empty while compacting, so a panic leaks elements rather than
+        // dropping them twice. the guard restores the length
+        self.end_ptr = self.begin_ptr;
+        let mut guard = RetainGuard {
+            vec: self,
+            len,
+            processed: 0,
+            deleted: 0,
+        };
+
+        while guard.processed < len {
+            let elem = unsafe { guard.vec.begin_ptr.add(guard.processed) };
+            let keep = f(unsafe { &mut *elem });
+            guard.processed += 1;
+            if !keep {
+                guard.deleted += 1;
+                unsafe { std::ptr::drop_in_place(elem) };
+            } else if guard.deleted > 0 {
+                unsafe {
+                    guard
+                        .vec
+                        .begin_ptr
+                        .add(guard.processed - 1 - guard.deleted)
+                        .copy_from_nonoverlapping(elem, 1)
+                };
+            }
+        }
+    }
+
+    /// Returns an iterator over chunks of `chunk_size` elements, starting
+    /// from the back of the vector. The last chunk yielded holds the
+    /// leftover elements at the front if the length isn't a multiple
+    ///
+    /// # Arguments
+    ///
+    /// `chunk_size`: The number of elements in each chunk, which must not be zero
+    pub fn rchunks(&self, chunk_size: usize) -> std::slice::RChunks<'_, T> {
+        self.as_slice().rchunks(chunk_size)
+    }
+
+    /// Returns an iterator over mutable chunks of `chunk_size` elements,
+    /// starting from the back of the vector. The last chunk yielded holds the
+    /// leftover elements at the front if the length isn't a multiple
+    ///
+    /// # Arguments
+    ///
+    /// `chunk_size`: The number of elements in each chunk, which must not be zero
+    pub fn rchunks_mut(&mut self, chunk_size: usize) -> std::slice::RChunksMut<'_, T> {
+        self.as_slice_mut().rchunks_mut(chunk_size)
+    }
+
+    /// Reserves space for at least `additional` more elements. The capacity
+    /// grows by at least as much as it would when pushing past it, so repeated
+    /// calls don't re-allocate every time
+    ///
+    /// # Arguments
+    ///
+    /// `additional`: The number of additional elements
     pub fn reserve(&mut self, additional: usize) {
-        if additional == 0 {
-            return;
+        let required_capacity = Self::required_capacity(self.len(), additional);
+        if required_capacity > self.capacity() {
+            self.realloc(required_capacity.max(Self::calculate_grow_capacity(self.capacity())));
         }
-        // allocate a new bit of memory
-        let size = self.len();
-        let new_capacity = self.capacity() + additional;
-        // allocate the new buffer
-        let new_begin_ptr = self.allocator.allocate::<T>(new_capacity);
-        // copy from the old array if we should
+    }
+
+    /// Reserves space for exactly `additional` more elements, without
+    /// rounding up. Does nothing if the capacity is already sufficient
+    ///
+    /// # Arguments
+    ///
+    /// `additional`: The number of additional elements
+    pub fn reserve_exact(&mut self, additional: usize) {
+        let required_capacity = Self::required_capacity(self.len(), additional);
+        if required_capacity > self.capacity() {
+            self.realloc(required_capacity);
+        }
+    }
+
+    /// Resizes the vector to `new_len` elements, calling `f` to produce each
+    /// new element when growing and dropping the excess when shrinking
+    ///
+    /// # Arguments
+    ///
+    /// `new_len`: The new length of the vector
+    ///
+    /// `f`: A function producing the new elements
+    pub fn resize_with<F: FnMut() -> T>(&mut self, new_len: usize, mut f: F) {
+        let len = self.len();
+        if new_len < len {
+            unsafe {
+                // shorten first so a panicking drop can't cause a double-drop
+                let tail =
+                    std::ptr::slice_from_raw_parts_mut(self.begin_ptr.add(new_len), len - new_len);
+                self.end_ptr = self.begin_ptr.add(new_len);
+                std::ptr::drop_in_place(tail);
+            }
+        } else {
+            self.reserve(new_len - len);
+            for _ in len..new_len {
+                self.push(f());
+            }
+        }
+    }
+
+    /// Rotates the vector in place so the element at `mid` becomes the first
+    ///
+    /// # Arguments
+    ///
+    /// `mid`: The number of elements to rotate left by
+    pub fn rotate_left(&mut self, mid: usize) {
+        let len = self.len();
+        assert!(
+            mid <= len,
+            "rotate amount ({mid}) out of bounds for vector of length {len}"
+        );
+        self.as_slice_mut().rotate_left(mid)
+    }
+
+    /// Rotates the vector in place so the last `k` elements move to the front
+    ///
+    /// # Arguments
+    ///
+    /// `k`: The number of elements to rotate right by
+    pub fn rotate_right(&mut self, k: usize) {
+        let len = self.len();
+        assert!(
+            k <= len,
+            "rotate amount ({k}) out of bounds for vector of length {len}"
+        );
+        self.as_slice_mut().rotate_right(k)
+    }
+
+    /// Sets the length of the vector without dropping or initializing any
+    /// elements. Usually used after writing to `spare_capacity_mut`
+    ///
+    /// # Arguments
+    ///
+    /// `new_len`: The new length of the vector
+    ///
+    /// # Safety
+    ///
+    /// `new_len` must be less than or equal to `capacity()`, and the elements
+    /// at `old_len..new_len` must be initialized. Elements past `new_len`
+    /// are forgotten rather than dropped
+    pub unsafe fn set_len(&mut self, new_len: usize) {
+        debug_assert!(new_len <= self.capacity());
         if !self.begin_ptr.is_null() {
+            self.end_ptr = self.begin_ptr.add(new_len);
+        }
+    }
+
+    /// Shrinks the capacity of the vector to its length, freeing the buffer
+    /// entirely if the vector is empty
+    pub fn shrink_to_fit(&mut self) {
+        if self.is_empty() {
+            self.clear();
+        } else if self.len() < self.capacity() {
+            self.realloc(self.len());
+        }
+    }
+
+    /// Returns the spare capacity of the vector past its length as
+    /// uninitialized elements. Filling them in and calling `set_len`
+    /// adds them to the vector without an extra copy
+    pub fn spare_capacity_mut(&mut self) -> &mut [MaybeUninit<T>] {
+        if self.begin_ptr.is_null() {
+            &mut []
+        } else {
             unsafe {
-                new_begin_ptr.copy_from(self.begin_ptr, size);
-                // deallocate the old memory
-                self.allocator.deallocate(self.begin_ptr, self.capacity());
+                std::slice::from_raw_parts_mut(
+                    self.end_ptr as *mut MaybeUninit<T>,
+                    self.capacity() - self.len(),
+                )
             }
         }
-        // calculate and store new pointers
-        self.begin_ptr = new_begin_ptr;
-        self.end_ptr = unsafe { new_begin_ptr.add(size) };
-        self.capacity_ptr = unsafe { new_begin_ptr.add(new_capacity) }
+    }
+
+    /// Swaps two elements in the vector
+    ///
+    /// # Arguments
+    ///
+    /// `a`: The index of the first element
+    ///
+    /// `b`: The index of the second element
+    pub fn swap(&mut self, a: usize, b: usize) {
+        let len = self.len();
+        assert!(
+            a < len,
+            "swap index `a` ({a}) out of bounds for vector of length {len}"
+        );
+        assert!(
+            b < len,
+            "swap index `b` ({b}) out of bounds for vector of length {len}"
+        );
+        self.as_slice_mut().swap(a, b)
+    }
+
+    /// Replaces a range of the vector with the elements of an iterator,
+    /// returning an iterator over the removed elements
+    ///
+    /// # Arguments
+    ///
+    /// `range`: The range of elements to remove
+    ///
+    /// `replace_with`: The elements to insert in place of the range
+    pub fn splice<R: RangeBounds<usize>, I: IntoIterator<Item = T>>(
+        &mut self,
+        range: R,
+        replace_with: I,
+    ) -> Splice<'_, T> {
+        let len = self.len();
+        let start = match range.start_bound() {
+            Bound::Included(&start) => start,
+            Bound::Excluded(&start) => start + 1,
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&end) => end + 1,
+            Bound::Excluded(&end) => end,
+            Bound::Unbounded => len,
+        };
+        assert!(start <= end && end <= len, "range out of bounds");
+
+        // push the replacements onto the end, in the vector's own storage. if the iterator
+        // panics, the guard drops them again and the vector is left untouched
+        let guard = SpliceGuard { vec: self, len };
+        for elem in replace_with {
+            guard.vec.push(elem);
+        }
+        std::mem::forget(guard);
+
+        let replaced = self.len() - len;
+        let removed = end - start;
+        let new_len = len - removed + replaced;
+        // an unallocated vector is empty and has nothing to replace
+        if len + replaced > start {
+            unsafe {
+                let region = std::slice::from_raw_parts_mut(
+                    self.begin_ptr.add(start),
+                    len + replaced - start,
+                );
+                // [removed][tail][replacements] -> [tail][replacements][removed]
+                region.rotate_left(removed);
+                // [tail][replacements] -> [replacements][tail]
+                region[..len - end + replaced].rotate_left(len - end);
+                // the removed elements now sit in the spare capacity until they're yielded
+                self.end_ptr = self.begin_ptr.add(new_len);
+            }
+        }
+
+        Splice {
+            removed: self.begin_ptr,
+            front: new_len,
+            back: new_len + removed,
+            _marker: PhantomData,
+        }
     }
 
     /// Incremement the array size
@@ -240,15 +648,59 @@ impl<T: Sized, A: Allocator> Vector<T, A> {
         if old_capacity == 0 {
             1
         } else {
-            old_capacity * 2
+            old_capacity.saturating_mul(2).min(Self::MAX_CAPACITY)
         }
     }
 
+    /// Calculates the capacity needed to fit additional elements, panicking
+    /// if it can't be represented
+    ///
+    /// # Arguments
+    ///
+    /// `len`: The current length of the array
+    ///
+    /// `additional`: The number of additional elements
+    fn required_capacity(len: usize, additional: usize) -> usize {
+        len.checked_add(additional)
+            .filter(|capacity| *capacity <= Self::MAX_CAPACITY)
+            .expect("capacity overflow")
+    }
+
     /// Grows the array to fit additional elements
     fn grow(&mut self) {
-        let new_capacity = Self::calculate_grow_capacity(self.capacity());
-        // reserve the additional needed capacity
-        self.reserve(new_capacity - self.capacity());
+        // the reservation rounds up to the growth policy, and catches overflow
+        self.reserve(1);
+    }
+
+    /// Moves the elements into a new buffer with the given capacity
+    ///
+    /// # Arguments
+    ///
+    /// `new_capacity`: The capacity of the new buffer, which must fit the elements
+    fn realloc(&mut self, new_capacity: usize) {
+        let size = self.len();
+        // allocate the new buffer
+        let new_begin_ptr = self.allocator.allocate::<T>(new_capacity);
+        // claim any slack the allocator rounded the buffer up with. zero-sized types
+        // have no buffer to round
+        let elem_size = std::mem::size_of::<T>();
+        let new_capacity = self
+            .allocator
+            .usable_size(new_capacity * elem_size, std::mem::align_of::<T>())
+            .checked_div(elem_size)
+            .map_or(new_capacity, |capacity| capacity.min(Self::MAX_CAPACITY));
+        // copy from the old array if we should
+        if !self.begin_ptr.is_null() {
+            unsafe {
+                new_begin_ptr.copy_from(self.begin_ptr, size);
+                // deallocate the old memory
+                self.allocator.deallocate(self.begin_ptr, self.capacity());
+            }
+        }
+        // calculate and store new pointers
+        self.begin_ptr = new_begin_ptr;
+        self.end_ptr = unsafe { new_begin_ptr.add(size) };
+        self.capacity_ptr = unsafe { new_begin_ptr.add(new_capacity) }
     }
 }
 
@@ -276,32 +728,317 @@ impl<T: Sized + Clone, A: Allocator> Vector<T, A> {
     ///
     /// `buf`: The buffer or elements.
     pub fn append(&mut self, buf: &[T]) {
-        let old_len = self.len();
-        let new_len = old_len + buf.len();
-        if new_len > self.capacity() {
-            self.reserve(new_len - self.capacity());
+        self.reserve(buf.len());
+
+        // clone in place
+        for elem in buf {
+            unsafe {
+                self.end_ptr.write(elem.clone());
+                self.increment_size();
+            }
+        }
+    }
+
+    /// Assigns a vector to a slice
+    ///
+    /// # Arguments
+    ///
+    /// `buf`: The slice
+    pub fn assign(&mut self, buf: &[T]) {
+        // drop the old elements, but keep the memory around
+        unsafe {
+            std::ptr::drop_in_place(self.as_slice_mut());
+        }
+        self.end_ptr = self.begin_ptr;
+
+        self.append(buf)
+    }
+
+    /// Clones the vector into a vector backed by a different allocator
+    ///
+    /// # Arguments
+    ///
+    /// `allocator`: The allocator used to allocate and de-allocate elements
+    ///
+    /// # Safety
+    ///
+    /// The allocator specified must safely allocate ande de-allocate valid memory
+    pub unsafe fn clone_in<A2: Allocator>(&self, allocator: A2) -> Vector<T, A2> {
+        Vector::from_in(self.as_slice(), allocator)
+    }
+}
+
+impl<T: PartialEq, A: Allocator> Vector<T, A> {
+    /// Returns true if the vector contains an element equal to `x`
+    ///
+    /// # Arguments
+    ///
+    /// `x`: The element to search for
+    pub fn contains(&self, x: &T) -> bool {
+        self.as_slice().contains(x)
+    }
+}
+
+impl<A: Allocator> Vector<bool, A> {
+    /// Returns the number of elements which are `true`
+    pub fn count_set(&self) -> usize {
+        self.iter().filter(|elem| **elem).count()
+    }
+
+    /// Sets every element in the vector to `value`
+    ///
+    /// # Arguments
+    ///
+    /// `value`: The value to set
+    pub fn set_all(&mut self, value: bool) {
+        self.as_slice_mut().fill(value)
+    }
+}
+
+impl<T: Clone, A: Allocator + Default, A2: Allocator> Vector<Vector<T, A>, A2> {
+    /// Flattens the vectors into a single vector, allocating once
+    pub fn concat(&self) -> Vector<T, A> {
+        let mut concatenated = Vector::with_capacity(self.iter().map(|vector| vector.len()).sum());
+        for vector in self {
+            concatenated.append(vector);
+        }
+        concatenated
+    }
+
+    /// Flattens the vectors into a single vector with a separator between
+    /// each of them, allocating once
+    ///
+    /// # Arguments
+    ///
+    /// `sep`: The elements to place between each vector
+    pub fn join(&self, sep: &[T]) -> Vector<T, A> {
+        let separators = self.len().saturating_sub(1) * sep.len();
+        let mut joined = Vector::with_capacity(
+            self.iter().map(|vector| vector.len()).sum::<usize>() + separators,
+        );
+        for (index, vector) in self.iter().enumerate() {
+            if index != 0 {
+                joined.append(sep);
+            }
+            joined.append(vector);
+        }
+        joined
+    }
+}
+
+impl<T: Pod, A: Allocator> Vector<T, A> {
+    /// Returns the raw bytes of the elements in the vector, in
+    /// native byte order
+    pub fn as_bytes(&self) -> &[u8] {
+        let slice = self.as_slice();
+        unsafe { std::slice::from_raw_parts(slice.as_ptr() as *const u8, size_of_val(slice)) }
+    }
+}
+
+impl<T: Pod> Vector<T, DefaultAllocator> {
+    /// Creates a vector by copying elements out of their raw bytes, in
+    /// native byte order. Returns `None` if the number of bytes is not a
+    /// multiple of the element size, or if the elements are zero-sized
+    /// arrays, since the number of elements can't be known. The bytes
+    /// don't need to be aligned
+    ///
+    /// # Arguments
+    ///
+    /// `bytes`: The raw bytes of the elements
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        if size_of::<T>() == 0 || !bytes.len().is_multiple_of(size_of::<T>()) {
+            return None;
+        }
+
+        let len = bytes.len() / size_of::<T>();
+        let mut v = Self::with_capacity(len);
+        if len > 0 {
+            unsafe {
+                (v.begin_ptr as *mut u8).copy_from_nonoverlapping(bytes.as_ptr(), bytes.len());
+                v.end_ptr = v.begin_ptr.add(len);
+            }
+        }
+        Some(v)
+    }
+}
+
+/// Marks types which are plain old data, and can be freely
+/// converted to and from raw bytes by `Vector::as_bytes` and
+/// `Vector::from_bytes`
+///
+/// # Safety
+///
+/// The type must have no padding bytes, and every bit pattern must
+/// be a valid value of the type. Only arrays may be zero-sized
+pub unsafe trait Pod: Copy {}
+
+#[duplicate_item(
+    pod_type;
+    [u8]; [u16]; [u32]; [u64]; [u128]; [usize];
+    [i8]; [i16]; [i32]; [i64]; [i128]; [isize];
+    [f32]; [f64];
+)]
+unsafe impl Pod for pod_type {}
+
+unsafe impl<T: Pod, const N: usize> Pod for [T; N] {}
+
+/// An iterator over the elements removed from a vector by `Vector::splice`.
+/// The removed elements are kept in the vector's spare capacity until they
+/// are yielded, and any left over are dropped with the iterator
+pub struct Splice<'a, T> {
+    removed: *mut T,
+    front: usize,
+    back: usize,
+    _marker: PhantomData<&'a mut [T]>,
+}
+
+impl<'a, T> Iterator for Splice<'a, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.front == self.back {
+            return None;
+        }
+        let elem = unsafe { self.removed.add(self.front).read() };
+        self.front += 1;
+        Some(elem)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.back - self.front;
+        (len, Some(len))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for Splice<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.front == self.back {
+            return None;
+        }
+        self.back -= 1;
+        Some(unsafe { self.removed.add(self.back).read() })
+    }
+}
+
+impl<'a, T> ExactSizeIterator for Splice<'a, T> {}
+
+impl<'a, T> Drop for Splice<'a, T> {
+    fn drop(&mut self) {
+        if self.front != self.back {
+            unsafe {
+                std::ptr::drop_in_place(std::ptr::slice_from_raw_parts_mut(
+                    self.removed.add(self.front),
+                    self.back - self.front,
+                ))
+            }
+        }
+    }
+}
+
+/// An iterator that moves elements out of a vector. It is double-ended, so
+/// the elements can also be taken from the back with `next_back` or `rev`
+pub struct IntoIter<T, A: Allocator> {
+    vec: Vector<T, A>,
+    front_ptr: *mut T,
+}
+
+impl<T, A: Allocator> Iterator for IntoIter<T, A> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.front_ptr == self.vec.end_ptr {
+            return None;
+        }
+
+        unsafe {
+            let elem = self.front_ptr.read();
+            self.front_ptr = self.front_ptr.add(1);
+            Some(elem)
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.len();
+        (len, Some(len))
+    }
+}
+
+impl<T, A: Allocator> DoubleEndedIterator for IntoIter<T, A> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.front_ptr == self.vec.end_ptr {
+            return None;
         }
 
-        // copy in place
         unsafe {
-            self.end_ptr = self.end_ptr.add(buf.len());
-            self.as_slice_mut()[old_len..old_len + buf.len()].clone_from_slice(buf);
+            self.vec.end_ptr = self.vec.end_ptr.sub(1);
+            Some(self.vec.end_ptr.read())
         }
     }
+}
 
-    /// Assigns a vector to a slice
-    ///
-    /// # Arguments
-    ///
-    /// `buf`: The slice
-    pub fn assign(&mut self, buf: &[T]) {
-        if buf.len() > self.capacity() {
-            self.reserve(buf.len() - self.capacity());
+impl<T, A: Allocator> ExactSizeIterator for IntoIter<T, A> {
+    fn len(&self) -> usize {
+        (unsafe { self.vec.end_ptr.offset_from(self.front_ptr) }) as usize
+    }
+}
+
+impl<T, A: Allocator> Drop for IntoIter<T, A> {
+    fn drop(&mut self) {
+        unsafe {
+            // drop the elements which were never yielded, then let the vector
+            // free its array without touching the moved-out elements
+            std::ptr::drop_in_place(std::ptr::slice_from_raw_parts_mut(
+                self.front_ptr,
+                self.len(),
+            ));
+            self.vec.end_ptr = self.vec.begin_ptr;
+        }
+    }
+}
+
+/// Drops the replacements pushed by `splice` if the replacement iterator panics
+struct SpliceGuard<'a, T, A: Allocator> {
+    vec: &'a mut Vector<T, A>,
+    len: usize,
+}
+
+impl<'a, T, A: Allocator> Drop for SpliceGuard<'a, T, A> {
+    fn drop(&mut self) {
+        let pushed = self.vec.len() - self.len;
+        if pushed > 0 {
+            unsafe {
+                std::ptr::drop_in_place(std::ptr::slice_from_raw_parts_mut(
+                    self.vec.begin_ptr.add(self.len),
+                    pushed,
+                ));
+                self.vec.end_ptr = self.vec.begin_ptr.add(self.len);
+            }
         }
+    }
+}
 
+/// Restores a vector's length once `retain` finishes or panics
+struct RetainGuard<'a, T, A: Allocator> {
+    vec: &'a mut Vector<T, A>,
+    len: usize,
+    processed: usize,
+    deleted: usize,
+}
+
+impl<'a, T, A: Allocator> Drop for RetainGuard<'a, T, A> {
+    fn drop(&mut self) {
         unsafe {
-            self.end_ptr = self.begin_ptr.add(buf.len());
-            self.as_slice_mut().clone_from_slice(buf);
+            // shift back any elements which were never visited
+            if self.deleted > 0 {
+                self.vec
+                    .begin_ptr
+                    .add(self.processed - self.deleted)
+                    .copy_from(
+                        self.vec.begin_ptr.add(self.processed),
+                        self.len - self.processed,
+                    );
+            }
+            self.vec.end_ptr = self.vec.begin_ptr.add(self.len - self.deleted);
         }
     }
 }
@@ -316,6 +1053,11 @@ impl<T: Clone, A: Allocator + Clone> Clone for Vector<T, A> {
     fn clone(&self) -> Self {
         unsafe { Self::from_in(self.as_slice(), self.allocator.clone()) }
     }
+
+    fn clone_from(&mut self, source: &Self) {
+        // keep our own buffer, which only re-allocates if it's too small
+        self.assign(source.as_slice())
+    }
 }
 
 impl<T: Debug, A: Allocator> Debug for Vector<T, A> {
@@ -333,6 +1075,20 @@ where
     }
 }
 
+impl<T: std::hash::Hash, A: Allocator> std::hash::Hash for Vector<T, A> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.as_slice().hash(state)
+    }
+}
+
+impl<T: PartialEq, A: Allocator> PartialEq for Vector<T, A> {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl<T: Eq, A: Allocator> Eq for Vector<T, A> {}
+
 impl<T, A: Allocator + Default> Default for Vector<T, A> {
     fn default() -> Self {
         unsafe { Vector::new_in(A::default()) }
@@ -390,8 +1146,13 @@ impl<T: Sized + Clone, const N: usize, A: Allocator + Default> From<&[T; N]> for
 impl<T, A: Allocator + Default> FromIterator<T> for Vector<T, A> {
     fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
         let iter = iter.into_iter();
-        let (lower_bound, _) = iter.size_hint();
-        let mut v = Vector::with_capacity(lower_bound);
+        // only trust the upper bound when it's tight, since a filtered range may have an
+        // enormous upper bound while yielding almost nothing
+        let (lower_bound, upper_bound) = iter.size_hint();
+        let capacity = upper_bound
+            .filter(|upper_bound| *upper_bound == lower_bound)
+            .unwrap_or(lower_bound);
+        let mut v = Vector::with_capacity(capacity);
         for elem in iter {
             v.push(elem);
         }
@@ -399,13 +1160,47 @@ impl<T, A: Allocator + Default> FromIterator<T> for Vector<T, A> {
     }
 }
 
+impl<T, A: Allocator> IntoIterator for Vector<T, A> {
+    type Item = T;
+    type IntoIter = IntoIter<T, A>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        Self::IntoIter {
+            front_ptr: self.begin_ptr,
+            vec: self,
+        }
+    }
+}
+
+impl<'a, T, A: Allocator> IntoIterator for &'a Vector<T, A> {
+    type Item = &'a T;
+    type IntoIter = std::slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T, A: Allocator> IntoIterator for &'a mut Vector<T, A> {
+    type Item = &'a mut T;
+    type IntoIter = std::slice::IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
 unsafe impl<T: Send, A: Allocator + Send> Send for Vector<T, A> {}
 unsafe impl<T: Sync, A: Allocator + Sync> Sync for Vector<T, A> {}
 
 #[cfg(test)]
 mod test {
-    use crate::vector::DefaultVector;
+    use crate::allocator::{Allocator, CountingAllocator};
+    use crate::util::DropCounter;
+    use crate::vector::{DefaultVector, Vector};
     use memoffset::offset_of;
+    use std::cell::Cell;
+    use std::rc::Rc;
 
     #[test]
     fn layout() {
@@ -526,6 +1321,56 @@ mod test {
         assert_eq!(v.iter().sum::<i32>(), 6);
     }
 
+    #[test]
+    fn iter_mut() {
+        let mut v = DefaultVector::from([1, 2, 3]);
+        v.iter_mut().for_each(|elem| *elem *= 2);
+        assert_eq!(&*v, &[2, 4, 6]);
+    }
+
+    #[test]
+    fn into_iter() {
+        let v = DefaultVector::from([1, 2, 3, 4]);
+        let mut iter = v.into_iter();
+        assert_eq!(iter.len(), 4);
+        assert_eq!(iter.next(), Some(1));
+        assert_eq!(iter.next_back(), Some(4));
+        assert_eq!(iter.collect::<Vec<_>>(), vec![2, 3]);
+
+        assert_eq!(DefaultVector::<u32>::new().into_iter().next(), None);
+    }
+
+    #[test]
+    fn into_iter_drops_remaining() {
+        let drops = Rc::new(Cell::new(0));
+        let v: DefaultVector<(u32, DropCounter)> =
+            (0..4).map(|n| (n, DropCounter(drops.clone()))).collect();
+        let mut iter = v.into_iter();
+        assert_eq!(iter.next().map(|elem| elem.0), Some(0));
+        assert_eq!(drops.get(), 1);
+        std::mem::drop(iter);
+        assert_eq!(drops.get(), 4);
+    }
+
+    #[test]
+    fn into_iter_ref() {
+        let v = DefaultVector::from([1, 2, 3]);
+        let mut sum = 0;
+        for elem in &v {
+            sum += *elem;
+        }
+        assert_eq!(sum, 6);
+    }
+
+    #[test]
+    fn into_iter_mut() {
+        let mut v = DefaultVector::from([1, 2, 3]);
+        for elem in &mut v {
+            *elem *= 2;
+        }
+        assert_eq!(&*v, &[2, 4, 6]);
+    }
+
     #[test]
     fn from() {
         let v = DefaultVector::from(&[1, 2, 3]);
@@ -613,4 +1458,583 @@ mod test {
         assert_eq!(v.capacity(), 6);
         assert_eq!(&*v, &[1, 2, 3, 4, 5, 6]);
     }
+
+    #[test]
+    fn splice_longer() {
+        let mut v = DefaultVector::from([1, 2, 3, 4, 5]);
+        let removed = v.splice(1..3, [7, 8, 9]).collect::<Vec<_>>();
+        assert_eq!(removed, [2, 3]);
+        assert_eq!(&*v, &[1, 7, 8, 9, 4, 5]);
+    }
+
+    #[test]
+    fn splice_empty() {
+        let mut v = DefaultVector::from([1, 2, 3, 4, 5]);
+        let removed = v.splice(1..3, []).collect::<Vec<_>>();
+        assert_eq!(removed, [2, 3]);
+        assert_eq!(&*v, &[1, 4, 5]);
+    }
+
+    #[test]
+    fn splice_back() {
+        let mut v = DefaultVector::from([1, 2, 3, 4, 5]);
+        let mut removed = v.splice(1..4, [7]);
+        assert_eq!(removed.len(), 3);
+        assert_eq!(removed.next_back(), Some(4));
+        assert_eq!(removed.next(), Some(2));
+        assert_eq!(removed.next_back(), Some(3));
+        assert_eq!(removed.next(), None);
+        std::mem::drop(removed);
+        assert_eq!(&*v, &[1, 7, 5]);
+    }
+
+    #[test]
+    fn splice_uses_own_allocator() {
+        let allocator = CountingAllocator::default();
+        let mut v = unsafe { Vector::from_in(&[1u32, 2, 3, 4, 5], allocator.clone()) };
+        let allocations = allocator.allocations();
+        let removed = v.splice(1..3, [7, 8, 9]).collect::<Vec<_>>();
+        assert_eq!(removed, [2, 3]);
+        assert_eq!(&*v, &[1, 7, 8, 9, 4, 5]);
+        // only the vector's own growth goes through the allocator
+        assert_eq!(allocator.allocations(), allocations + 1);
+    }
+
+    #[test]
+    fn splice_drops_remaining() {
+        let drops = Rc::new(Cell::new(0));
+        let mut v: DefaultVector<(u32, DropCounter)> =
+            (0..4).map(|n| (n, DropCounter(drops.clone()))).collect();
+        let mut removed = v.splice(1..3, []);
+        assert_eq!(removed.next().map(|elem| elem.0), Some(1));
+        assert_eq!(drops.get(), 1);
+        std::mem::drop(removed);
+        assert_eq!(drops.get(), 2);
+        assert_eq!(v.iter().map(|elem| elem.0).collect::<Vec<_>>(), [0, 3]);
+    }
+
+    #[test]
+    fn splice_panic_keeps_vector() {
+        let drops = Rc::new(Cell::new(0));
+        let mut v: DefaultVector<(u32, DropCounter)> =
+            (0..2).map(|n| (n, DropCounter(drops.clone()))).collect();
+        let replacements = (2..4).map(|n| {
+            if n == 3 {
+                panic!("replacement failed");
+            }
+            (n, DropCounter(drops.clone()))
+        });
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            v.splice(..1, replacements);
+        }));
+        assert!(result.is_err());
+        // the pushed replacement was dropped and the vector is untouched
+        assert_eq!(drops.get(), 1);
+        assert_eq!(v.iter().map(|elem| elem.0).collect::<Vec<_>>(), [0, 1]);
+    }
+
+    #[test]
+    fn splice_unallocated() {
+        let mut v = DefaultVector::<u32>::new();
+        assert_eq!(v.splice(.., []).count(), 0);
+        assert!(v.is_empty());
+    }
+
+    #[test]
+    fn clone_in() {
+        let v = DefaultVector::from([1u32, 2, 3]);
+        let allocator = CountingAllocator::default();
+
+        let cloned = unsafe { v.clone_in(allocator.clone()) };
+        assert_eq!(&*cloned, &[1, 2, 3]);
+        assert_eq!(allocator.allocations(), 1);
+        assert_eq!(allocator.live_bytes(), 3 * std::mem::size_of::<u32>());
+
+        std::mem::drop(cloned);
+        assert_eq!(allocator.live_bytes(), 0);
+    }
+
+    #[test]
+    fn swap() {
+        let mut v = DefaultVector::from([1, 2, 3]);
+        v.swap(0, 2);
+        assert_eq!(&*v, &[3, 2, 1]);
+    }
+
+    #[test]
+    #[should_panic(expected = "swap index `b` (3) out of bounds for vector of length 3")]
+    fn swap_out_of_bounds() {
+        let mut v = DefaultVector::from([1, 2, 3]);
+        v.swap(0, 3);
+    }
+
+    #[test]
+    fn contains() {
+        let v = DefaultVector::from([1, 2, 3]);
+        assert!(v.contains(&2));
+        assert!(!v.contains(&4));
+    }
+
+    #[test]
+    fn raw_ptr_access() {
+        let mut v: DefaultVector<u32> = [1, 2, 3].into();
+        assert_eq!(unsafe { *v.as_ptr().add(2) }, 3);
+
+        unsafe { *v.as_mut_ptr().add(1) = 5 };
+        assert_eq!(v.as_slice(), &[1, 5, 3]);
+
+        let empty: DefaultVector<u32> = DefaultVector::new();
+        assert!(empty.as_ptr().is_null());
+    }
+
+    #[test]
+    fn std_hash() {
+        let mut set = std::collections::HashSet::new();
+        set.insert(DefaultVector::<u8>::from(b"abc"));
+
+        assert!(set.contains(&DefaultVector::from(b"abc")));
+        assert!(!set.contains(&DefaultVector::from(b"abd")));
+    }
+
+    #[test]
+    fn retain() {
+        let mut v: DefaultVector<u32> = (0..10).collect();
+        v.retain(|n| n % 2 == 0);
+        assert_eq!(v.as_slice(), &[0, 2, 4, 6, 8]);
+
+        let mut empty: DefaultVector<u32> = DefaultVector::new();
+        empty.retain(|_| false);
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn retain_mut() {
+        let mut v: DefaultVector<u32> = (0..10).collect();
+        v.retain_mut(|n| {
+            *n *= 2;
+            *n % 4 == 0
+        });
+        assert_eq!(v.as_slice(), &[0, 4, 8, 12, 16]);
+    }
+
+    #[test]
+    fn retain_mut_panic() {
+        let drops = Rc::new(Cell::new(0));
+        let mut v: DefaultVector<(u32, DropCounter)> =
+            (0..10).map(|n| (n, DropCounter(drops.clone()))).collect();
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            v.retain_mut(|elem| {
+                assert_ne!(elem.0, 5, "predicate panicked");
+                elem.0 % 2 == 0
+            })
+        }));
+        assert!(result.is_err());
+
+        // the odd elements before the panic were removed, the rest are untouched
+        assert_eq!(drops.get(), 2);
+        assert!(v.iter().map(|elem| elem.0).eq([0, 2, 4, 5, 6, 7, 8, 9]));
+        std::mem::drop(v);
+        assert_eq!(drops.get(), 10);
+    }
+
+    #[test]
+    fn rotate() {
+        let mut v: DefaultVector<u32> = (0..5).collect();
+        v.rotate_left(2);
+        assert_eq!(v.as_slice(), &[2, 3, 4, 0, 1]);
+
+        v.rotate_right(2);
+        assert_eq!(v.as_slice(), &[0, 1, 2, 3, 4]);
+
+        v.rotate_right(1);
+        assert_eq!(v.as_slice(), &[4, 0, 1, 2, 3]);
+    }
+
+    #[test]
+    #[should_panic(expected = "rotate amount (6) out of bounds for vector of length 5")]
+    fn rotate_out_of_bounds() {
+        let mut v: DefaultVector<u32> = (0..5).collect();
+        v.rotate_left(6);
+    }
+
+    #[test]
+    fn reserve() {
+        let mut v: DefaultVector<u32> = DefaultVector::with_capacity(4);
+        assert_eq!(v.capacity(), 4);
+        (1..=4).for_each(|n| v.push(n));
+
+        // growing by one element rounds up to the doubling policy
+        v.reserve(1);
+        assert_eq!(v.capacity(), 8);
+        // there's already room, so nothing changes
+        v.reserve(4);
+        assert_eq!(v.capacity(), 8);
+        // a large request is satisfied exactly
+        v.reserve(20);
+        assert_eq!(v.capacity(), 24);
+        assert_eq!(v.as_slice(), &[1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn reserve_exact() {
+        let mut v: DefaultVector<u32> = [1, 2, 3, 4].into();
+        v.reserve_exact(1);
+        assert_eq!(v.capacity(), 5);
+        v.reserve_exact(1);
+        assert_eq!(v.capacity(), 5);
+        v.reserve_exact(3);
+        assert_eq!(v.capacity(), 7);
+        assert_eq!(v.as_slice(), &[1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn first_last() {
+        let mut v: DefaultVector<u32> = DefaultVector::new();
+        assert_eq!(v.first(), None);
+        assert_eq!(v.last(), None);
+        assert_eq!(v.first_mut(), None);
+        assert_eq!(v.last_mut(), None);
+
+        v.push(1);
+        v.push(2);
+        v.push(3);
+        assert_eq!(v.first(), Some(&1));
+        assert_eq!(v.last(), Some(&3));
+
+        *v.last_mut().unwrap() = 5;
+        *v.first_mut().unwrap() += 1;
+        assert_eq!(v.as_slice(), &[2, 2, 5]);
+    }
+
+    #[test]
+    #[should_panic(expected = "capacity overflow")]
+    fn reserve_overflow() {
+        let mut v: DefaultVector<u32> = [1].into();
+        v.reserve(usize::MAX);
+    }
+
+    #[test]
+    #[should_panic(expected = "capacity overflow")]
+    fn reserve_exact_too_large() {
+        let mut v: DefaultVector<u32> = DefaultVector::new();
+        // representable as a length, but too large to allocate
+        v.reserve_exact(usize::MAX / 2);
+    }
+
+    #[test]
+    fn grow_capacity_saturates() {
+        assert_eq!(
+            DefaultVector::<u32>::calculate_grow_capacity(usize::MAX / 2),
+            isize::MAX as usize / 4
+        );
+    }
+
+    #[test]
+    fn from_iter_exact_size() {
+        let v: Vector<u32, CountingAllocator> = vec![1, 2, 3, 4, 5].into_iter().collect();
+        assert_eq!(&*v, &[1, 2, 3, 4, 5]);
+        assert_eq!(v.capacity(), 5);
+        assert_eq!(v.allocator.allocations(), 1);
+    }
+
+    #[test]
+    fn from_iter_loose_upper_bound() {
+        let v: DefaultVector<u32> = (0..100).filter(|n| n % 25 == 0).collect();
+        assert_eq!(&*v, &[0, 25, 50, 75]);
+
+        let v: DefaultVector<u32> = (0..100).filter(|_| false).collect();
+        assert!(v.is_empty());
+        assert_eq!(v.capacity(), 0);
+    }
+
+    #[test]
+    fn from_iter_huge_upper_bound() {
+        // reserving the upper bound here would be terabytes
+        let v: DefaultVector<u64> = (0..1u64 << 40)
+            .filter(|n| n % 1000 == 0)
+            .take_while(|n| *n < 5000)
+            .collect();
+        assert_eq!(&*v, &[0, 1000, 2000, 3000, 4000]);
+        assert!(v.capacity() < 16);
+    }
+
+    #[test]
+    fn std_vec_round_trip() {
+        let elem = Rc::new(5);
+        let v = DefaultVector::from([elem.clone(), Rc::new(6), Rc::new(7)]);
+
+        let vec = v.into_std_vec();
+        assert!(vec.iter().map(|elem| **elem).eq([5, 6, 7]));
+
+        let v = DefaultVector::from_std_vec(vec);
+        assert!(v.iter().map(|elem| **elem).eq([5, 6, 7]));
+        assert_eq!(Rc::strong_count(&elem), 2);
+
+        std::mem::drop(v);
+        assert_eq!(Rc::strong_count(&elem), 1);
+
+        assert!(DefaultVector::<u32>::from_std_vec(Vec::new())
+            .into_std_vec()
+            .is_empty());
+    }
+
+    #[test]
+    fn rchunks() {
+        let mut v = DefaultVector::from([1, 2, 3, 4, 5]);
+        assert!(v.rchunks(2).eq([&[4, 5][..], &[2, 3], &[1]]));
+
+        v.rchunks_mut(2).for_each(|chunk| chunk.reverse());
+        assert_eq!(&*v, &[1, 3, 2, 5, 4]);
+    }
+
+    #[test]
+    fn chunks_exact_mut() {
+        let mut v: DefaultVector<u32> = (0..8).collect();
+        let mut chunks = v.chunks_exact_mut(3);
+        for chunk in chunks.by_ref() {
+            chunk.reverse();
+        }
+        chunks.into_remainder().fill(0);
+
+        assert_eq!(&*v, &[2, 1, 0, 5, 4, 3, 0, 0]);
+        assert!(v.windows(2).take(2).all(|w| w[0] > w[1]));
+    }
+
+    #[test]
+    fn push_mut() {
+        let mut v = DefaultVector::new();
+        *v.push_mut(1u32) += 10;
+        v.push(2);
+        let last = v.push_mut(3);
+        *last *= 10;
+
+        assert_eq!(&*v, &[11, 2, 30]);
+    }
+
+    #[test]
+    fn append_vector() {
+        let mut v = DefaultVector::from([1u32, 2]);
+        let mut other = DefaultVector::from([3, 4, 5]);
+        v.append_vector(&mut other);
+        assert_eq!(&*v, &[1, 2, 3, 4, 5]);
+        assert!(other.is_empty());
+
+        // an empty vector takes the whole buffer from a compatible allocator
+        let mut empty = DefaultVector::new();
+        let buffer = v.as_ptr();
+        empty.append_vector(&mut v);
+        assert_eq!(empty.as_ptr(), buffer);
+        assert_eq!(&*empty, &[1, 2, 3, 4, 5]);
+        assert!(v.is_empty());
+    }
+
+    #[test]
+    fn append_vector_incompatible() {
+        let first = CountingAllocator::default();
+        let second = CountingAllocator::default();
+        assert!(first.is_compatible(&first.clone()));
+        assert!(!first.is_compatible(&second));
+
+        let elem = Rc::new(1);
+        let mut v: Vector<Rc<u32>, _> = unsafe { Vector::new_in(first.clone()) };
+        let mut other = unsafe { Vector::new_in(second.clone()) };
+        other.push(elem.clone());
+        other.push(Rc::new(2));
+
+        // the buffer was allocated by `second`, so the elements are moved instead
+        v.append_vector(&mut other);
+        assert!(v.iter().map(|elem| **elem).eq([1, 2]));
+        assert!(other.is_empty());
+        assert!(other.capacity() > 0);
+        assert_eq!(Rc::strong_count(&elem), 2);
+
+        std::mem::drop(v);
+        std::mem::drop(other);
+        assert_eq!(Rc::strong_count(&elem), 1);
+        assert_eq!(first.live_bytes(), 0);
+        assert_eq!(second.live_bytes(), 0);
+    }
+
+    #[test]
+    fn resize_with() {
+        let drops = Rc::new(Cell::new(0));
+        let mut next_id = 0;
+        let mut v = DefaultVector::new();
+        v.resize_with(10, || {
+            next_id += 1;
+            (next_id, DropCounter(drops.clone()))
+        });
+        assert_eq!(v.len(), 10);
+        assert!(v.iter().map(|elem| elem.0).eq(1..=10));
+
+        v.resize_with(4, || unreachable!());
+        assert_eq!(drops.get(), 6);
+        assert!(v.iter().map(|elem| elem.0).eq(1..=4));
+
+        v.resize_with(0, || unreachable!());
+        assert!(v.is_empty());
+        assert_eq!(drops.get(), 10);
+    }
+
+    #[test]
+    fn bool_mask() {
+        let mut v: DefaultVector<bool> = (0..10).map(|n| n % 3 == 0).collect();
+        assert_eq!(v.count_set(), 4);
+
+        v.set_all(true);
+        assert_eq!(v.count_set(), 10);
+        v.set_all(false);
+        assert_eq!(v.count_set(), 0);
+        assert_eq!(v.len(), 10);
+    }
+
+    #[test]
+    fn clone_from() {
+        let source =
+            unsafe { DefaultVector::from([1u32, 2, 3]).clone_in(CountingAllocator::default()) };
+
+        let allocator = CountingAllocator::default();
+        let mut v = unsafe { Vector::new_in(allocator.clone()) };
+        v.reserve_exact(4);
+        v.push(9);
+        assert_eq!(allocator.allocations(), 1);
+
+        // the existing buffer is large enough to hold the source
+        v.clone_from(&source);
+        assert_eq!(&*v, &[1, 2, 3]);
+        assert_eq!(v.capacity(), 4);
+        assert_eq!(allocator.allocations(), 1);
+
+        // an empty vector has to allocate
+        let mut v = unsafe { Vector::new_in(allocator.clone()) };
+        v.clone_from(&source);
+        assert_eq!(&*v, &[1, 2, 3]);
+        assert_eq!(allocator.allocations(), 2);
+    }
+
+    #[test]
+    fn shrink_to_fit() {
+        let mut v = DefaultVector::with_capacity(10);
+        v.push(1u32);
+        v.push(2);
+        v.shrink_to_fit();
+        assert_eq!(v.capacity(), 2);
+        assert_eq!(&*v, &[1, 2]);
+
+        v.pop();
+        v.pop();
+        v.shrink_to_fit();
+        assert_eq!(v.capacity(), 0);
+        assert!(v.as_ptr().is_null());
+    }
+
+    #[test]
+    #[cfg(target_endian = "little")]
+    fn bytes_round_trip() {
+        let v = DefaultVector::from([1u32, 0x01020304, u32::MAX]);
+        let bytes = v.as_bytes();
+        assert_eq!(bytes.len(), 12);
+        assert_eq!(&bytes[..8], &[1, 0, 0, 0, 4, 3, 2, 1]);
+
+        let round_trip = DefaultVector::<u32>::from_bytes(bytes).unwrap();
+        assert_eq!(round_trip, v);
+
+        // unaligned bytes are copied fine
+        let mut buf = vec![0u8];
+        buf.extend_from_slice(bytes);
+        assert_eq!(DefaultVector::<u32>::from_bytes(&buf[1..]).unwrap(), v);
+    }
+
+    #[test]
+    fn from_bytes_bad_len() {
+        assert!(DefaultVector::<u32>::from_bytes(&[0; 7]).is_none());
+
+        let empty = DefaultVector::<u32>::from_bytes(&[]).unwrap();
+        assert!(empty.is_empty());
+        assert!(empty.as_bytes().is_empty());
+    }
+
+    #[test]
+    fn from_bytes_zero_sized() {
+        assert!(DefaultVector::<[u8; 0]>::from_bytes(&[]).is_none());
+        assert!(DefaultVector::<[u8; 0]>::from_bytes(&[0; 4]).is_none());
+    }
+
+    #[test]
+    fn spare_capacity_mut() {
+        let mut v = DefaultVector::new();
+        assert!(v.spare_capacity_mut().is_empty());
+
+        v.push(0u32);
+        v.reserve_exact(3);
+        let spare = v.spare_capacity_mut();
+        assert_eq!(spare.len(), 3);
+        for (n, elem) in spare.iter_mut().enumerate() {
+            elem.write(n as u32 + 1);
+        }
+
+        unsafe { v.set_len(4) };
+        assert_eq!(&*v, &[0, 1, 2, 3]);
+        assert!(v.spare_capacity_mut().is_empty());
+
+        unsafe { v.set_len(2) };
+        assert_eq!(&*v, &[0, 1]);
+    }
+
+    #[test]
+    fn take() {
+        let mut v = DefaultVector::from([1u32, 2, 3]);
+        let ptr = v.as_ptr();
+
+        let taken = v.take();
+        assert_eq!(&*taken, &[1, 2, 3]);
+        assert_eq!(taken.as_ptr(), ptr);
+
+        assert!(v.is_empty());
+        assert!(v.begin_ptr.is_null());
+        assert!(v.end_ptr.is_null());
+        assert!(v.capacity_ptr.is_null());
+
+        // `mem::take` goes through `Default`, which is just as cheap
+        let mut v = taken;
+        let taken = std::mem::take(&mut v);
+        assert_eq!(taken.as_ptr(), ptr);
+        assert!(v.begin_ptr.is_null());
+    }
+
+    #[test]
+    fn first_last_chunk() {
+        let v = DefaultVector::from(b"EASTLdata");
+        assert_eq!(v.first_chunk::<4>(), Some(b"EAST"));
+        assert_eq!(v.last_chunk::<4>(), Some(b"data"));
+        assert_eq!(v.first_chunk::<9>(), Some(b"EASTLdata"));
+        assert_eq!(v.first_chunk::<0>(), Some(&[]));
+
+        // too short
+        assert_eq!(v.first_chunk::<10>(), None);
+        assert_eq!(v.last_chunk::<10>(), None);
+        assert_eq!(DefaultVector::<u8>::new().first_chunk::<4>(), None);
+    }
+
+    #[test]
+    fn concat_join() {
+        let v: DefaultVector<DefaultVector<u32>> = [
+            DefaultVector::from([1, 2]),
+            DefaultVector::from([3]),
+            DefaultVector::from([4, 5]),
+        ]
+        .into();
+
+        let concatenated = v.concat();
+        assert_eq!(concatenated.as_slice(), &[1, 2, 3, 4, 5]);
+        assert_eq!(concatenated.capacity(), 5);
+
+        let joined = v.join(&[0, 0]);
+        assert_eq!(joined.as_slice(), &[1, 2, 0, 0, 3, 0, 0, 4, 5]);
+        assert_eq!(joined.capacity(), 9);
+
+        let empty: DefaultVector<DefaultVector<u32>> = DefaultVector::new();
+        assert!(empty.concat().is_empty());
+        assert!(empty.join(&[0]).is_empty());
+    }
 }
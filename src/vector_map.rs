@@ -1,6 +1,7 @@
 use crate::allocator::{Allocator, DefaultAllocator};
 use crate::compare::{Compare, Less};
-use crate::vector::Vector;
+use crate::vector::{self, Vector};
+use std::borrow::Borrow;
 use std::cmp::Ordering;
 use std::fmt::{Debug, Formatter};
 use std::ops::Deref;
@@ -54,7 +55,11 @@ impl<K: PartialEq, V, A: Allocator, C: Compare<K> + Default> VectorMap<K, V, A,
     /// # Arguments
     ///
     /// `key`: The key to search for
-    pub fn contains_key(&self, key: &K) -> bool {
+    pub fn contains_key<Q: ?Sized + PartialEq>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        C: Compare<Q>,
+    {
         self.get(key).is_some()
     }
 
@@ -63,14 +68,18 @@ impl<K: PartialEq, V, A: Allocator, C: Compare<K> + Default> VectorMap<K, V, A,
     /// # Arguments
     ///
     /// `key`: The key to search for
-    pub fn get(&self, key: &K) -> Option<&V> {
+    pub fn get<Q: ?Sized + PartialEq>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        C: Compare<Q>,
+    {
         let lower_bound = self.lower_bound(key);
 
         // make sure the bound is in-range
         if lower_bound < self.len() {
             let (k, v) = &self.base[lower_bound];
 
-            if k == key {
+            if k.borrow() == key {
                 Some(v)
             } else {
                 None
@@ -85,14 +94,18 @@ impl<K: PartialEq, V, A: Allocator, C: Compare<K> + Default> VectorMap<K, V, A,
     /// # Arguments
     ///
     /// `key`: The key to search for
-    pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+    pub fn get_mut<Q: ?Sized + PartialEq>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        C: Compare<Q>,
+    {
         let lower_bound = self.lower_bound(key);
 
         // make sure the bound is in-range
         if lower_bound < self.len() {
             let (k, v) = &mut self.base[lower_bound];
 
-            if k == key {
+            if (*k).borrow() == key {
                 Some(v)
             } else {
                 None
@@ -126,6 +139,35 @@ impl<K: PartialEq, V, A: Allocator, C: Compare<K> + Default> VectorMap<K, V, A,
         }
     }
 
+    /// Inserts the key-value pair into the vector map, returning the old value.
+    /// If the key belongs at `hint` or just after it, the binary search is
+    /// skipped, which speeds up inserting keys in order
+    ///
+    /// # Arguments
+    ///
+    /// `hint`: The index the key is expected to be inserted near, such as the
+    /// index of the previously inserted key
+    ///
+    /// `key`: The key with which to insert the pair
+    ///
+    /// `value`: The associated value
+    pub fn insert_with_hint(&mut self, hint: usize, key: K, mut value: V) -> Option<V> {
+        let lower_bound = [hint, hint.saturating_add(1)]
+            .into_iter()
+            .find(|index| self.is_lower_bound(*index, &key))
+            .unwrap_or_else(|| self.lower_bound(&key));
+
+        if lower_bound < self.len() && self.base[lower_bound].0 == key {
+            std::mem::swap(&mut value, &mut self.base[lower_bound].1);
+
+            Some(value)
+        } else {
+            self.base.insert(lower_bound, (key, value));
+
+            None
+        }
+    }
+
     /// Returns true if the hash map is empty
     pub fn is_empty(&self) -> bool {
         self.base.is_empty()
@@ -154,29 +196,65 @@ impl<K: PartialEq, V, A: Allocator, C: Compare<K> + Default> VectorMap<K, V, A,
 
     /// Removes a key-value pair from the hash map,
     /// returning the element if it was found
-    pub fn remove(&mut self, key: &K) -> Option<V> {
+    pub fn remove<Q: ?Sized + PartialEq>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        C: Compare<Q>,
+    {
         self.remove_entry(key).map(|(_, v)| v)
     }
 
     /// Removes a key-value pair from the hash map,
     /// returning the pair if it was found
-    pub fn remove_entry(&mut self, key: &K) -> Option<(K, V)> {
+    pub fn remove_entry<Q: ?Sized + PartialEq>(&mut self, key: &Q) -> Option<(K, V)>
+    where
+        K: Borrow<Q>,
+        C: Compare<Q>,
+    {
         // find the entry
         let lower_bound = self.lower_bound(key);
 
-        if lower_bound < self.len() && &self.base[lower_bound].0 == key {
+        if lower_bound < self.len() && self.base[lower_bound].0.borrow() == key {
             self.base.remove(lower_bound)
         } else {
             None
         }
     }
 
+    /// Retains only the entries that satisfy a predicate. The entries stay
+    /// sorted since their relative order is preserved
+    ///
+    /// # Arguments
+    ///
+    /// `f`: The predicate, which returns false for entries to remove
+    pub fn retain<F: FnMut(&K, &mut V) -> bool>(&mut self, mut f: F) {
+        self.base.retain_mut(|(k, v)| f(k, v))
+    }
+
+    /// Returns true if `index` is the index of the first value which is not
+    /// smaller than the key
+    ///
+    /// # Arguments
+    ///
+    /// `index`: The index to check
+    ///
+    /// `key`: The key to search for
+    fn is_lower_bound(&self, index: usize, key: &K) -> bool {
+        index <= self.len()
+            && (index == 0 || C::compare(&self.base[index - 1].0, key))
+            && (index == self.len() || !C::compare(&self.base[index].0, key))
+    }
+
     /// Finds the index of the first value which is not smaller
-    fn lower_bound(&self, key: &K) -> usize {
+    fn lower_bound<Q: ?Sized + PartialEq>(&self, key: &Q) -> usize
+    where
+        K: Borrow<Q>,
+        C: Compare<Q>,
+    {
         self.base.as_slice().lower_bound_by(|(k, _)| {
             // we don't perform an equality check here because we shouldn't need to. in a
             // lower bound, equal and less are the same thing
-            if C::compare(k, key) {
+            if C::compare(k.borrow(), key) {
                 Ordering::Less
             } else {
                 Ordering::Greater
@@ -272,6 +350,63 @@ impl<K: PartialEq + PartialOrd, V, A: Allocator + Default> FromIterator<(K, V)>
     }
 }
 
+impl<K: PartialEq, V, A: Allocator, C: Compare<K>> IntoIterator for VectorMap<K, V, A, C> {
+    type Item = (K, V);
+    type IntoIter = vector::IntoIter<(K, V), A>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.base.into_iter()
+    }
+}
+
+impl<'a, K: PartialEq, V, A: Allocator, C: Compare<K>> IntoIterator for &'a VectorMap<K, V, A, C> {
+    type Item = &'a (K, V);
+    type IntoIter = std::slice::Iter<'a, (K, V)>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.base.iter()
+    }
+}
+
+impl<'a, K: PartialEq, V, A: Allocator, C: Compare<K>> IntoIterator
+    for &'a mut VectorMap<K, V, A, C>
+{
+    type Item = (&'a K, &'a mut V);
+    type IntoIter = IterMut<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IterMut {
+            iter: self.base.iter_mut(),
+        }
+    }
+}
+
+/// An iterator over the pairs of a vector map in sorted order, with mutable
+/// values. Keys stay immutable so the ordering can't be broken
+pub struct IterMut<'a, K, V> {
+    iter: std::slice::IterMut<'a, (K, V)>,
+}
+
+impl<'a, K, V> Iterator for IterMut<'a, K, V> {
+    type Item = (&'a K, &'a mut V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|(k, v)| (&*k, v))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<'a, K, V> DoubleEndedIterator for IterMut<'a, K, V> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter.next_back().map(|(k, v)| (&*k, v))
+    }
+}
+
+impl<'a, K, V> ExactSizeIterator for IterMut<'a, K, V> {}
+
 unsafe impl<K: PartialEq + Send, V: Send, A: Allocator + Send, C: Compare<K> + Send> Send
     for VectorMap<K, V, A, C>
 {
@@ -283,6 +418,7 @@ unsafe impl<K: PartialEq + Sync, V: Sync, A: Allocator + Sync, C: Compare<K> + S
 
 #[cfg(test)]
 mod test {
+    use crate::string::DefaultString;
     use crate::vector_map::DefaultVectorMap;
 
     #[test]
@@ -366,6 +502,18 @@ mod test {
         assert_eq!(vec.get_mut(&6), None);
     }
 
+    #[test]
+    fn get_borrowed() {
+        let mut vec: DefaultVectorMap<DefaultString, u32> =
+            [("abc".into(), 1), ("def".into(), 2)].into_iter().collect();
+
+        assert!(vec.contains_key("abc"));
+        assert_eq!(vec.get("def"), Some(&2));
+        assert_eq!(vec.get("ghi"), None);
+        assert_eq!(vec.remove("abc"), Some(1));
+        assert!(!vec.contains_key("abc"));
+    }
+
     #[test]
     fn insert_less() {
         let mut vec = DefaultVectorMap::default();
@@ -378,6 +526,26 @@ mod test {
         assert_eq!(&*vec, &[(4, 5), (5, 6)]);
     }
 
+    #[test]
+    fn insert_with_hint() {
+        let mut vec: DefaultVectorMap<u32, u32> = DefaultVectorMap::new();
+        for n in 0..100 {
+            // the previous key was inserted just before this one belongs
+            let hint = (n as usize).saturating_sub(1);
+            assert_eq!(vec.insert_with_hint(hint, n, n * 2), None);
+        }
+        assert!(vec.iter().map(|(k, _)| *k).eq(0..100));
+
+        // a replaced key reports the old value
+        assert_eq!(vec.insert_with_hint(50, 50, 0), Some(100));
+        // bad hints fall back to the binary search
+        assert_eq!(vec.insert_with_hint(0, 200, 1), None);
+        assert_eq!(vec.insert_with_hint(1000, 99, 2), Some(198));
+        assert_eq!(vec.len(), 101);
+        assert!(vec.windows(2).all(|w| w[0].0 < w[1].0));
+        assert_eq!(vec.get(&50), Some(&0));
+    }
+
     #[test]
     fn iter() {
         let vec = DefaultVectorMap::from([(5, 6), (4, 7)]);
@@ -386,6 +554,30 @@ mod test {
         assert_eq!(vec.iter().len(), 2);
     }
 
+    #[test]
+    fn into_iter() {
+        let vec = DefaultVectorMap::from([(3, 'c'), (1, 'a'), (2, 'b')]);
+        assert_eq!(
+            vec.into_iter().collect::<Vec<_>>(),
+            vec![(1, 'a'), (2, 'b'), (3, 'c')]
+        );
+    }
+
+    #[test]
+    fn into_iter_borrowed() {
+        let mut vec = DefaultVectorMap::from([(2, 20), (1, 10)]);
+        let mut keys = Vec::new();
+        for (k, _) in &vec {
+            keys.push(*k);
+        }
+        assert_eq!(keys, vec![1, 2]);
+
+        for (k, v) in &mut vec {
+            *v += *k;
+        }
+        assert_eq!(&*vec, &[(1, 11), (2, 22)]);
+    }
+
     #[test]
     fn big_test() {
         let vec: DefaultVectorMap<_, _> = (0..50)
@@ -398,4 +590,18 @@ mod test {
         assert!(vec.windows(2).all(|w| w[0].0 < w[1].0));
         assert_eq!(vec.len(), 100);
     }
+
+    #[test]
+    fn retain() {
+        let mut map: DefaultVectorMap<u32, u32> = (0..50).map(|n| (n, n)).collect();
+        map.retain(|k, v| {
+            *v *= 2;
+            k % 2 == 0
+        });
+
+        assert_eq!(map.len(), 25);
+        assert!(map.iter().map(|(k, _)| *k).eq((0..50).step_by(2)));
+        assert_eq!(map.get(&10), Some(&20));
+        assert_eq!(map.get(&11), None);
+    }
 }